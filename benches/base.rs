@@ -14,12 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
 
+// Benchmarks need a toolchain newer than the crate's MSRV anyway (`divan` requirement).
+#![allow(clippy::incompatible_msrv)]
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-use fflonk_verifier::{verify, Proof, VerificationKey};
+use fflonk_verifier::{
+    verify, verify_batch, verify_many, verify_prepared, PreparedVerificationKey, Proof, Public,
+    VerificationKey,
+};
 use hex_literal::hex;
 
 static VALID_PROOF_DATA: [u8; 768] = hex!(
@@ -70,3 +76,42 @@ fn fflonk_verifier_with_base_key() -> bool {
         divan::black_box(VALID_PUBS_DATA),
     )
 }
+
+#[divan::bench]
+fn fflonk_verifier_with_prepared_base_key(bencher: divan::Bencher) {
+    let vk = PreparedVerificationKey::from(VerificationKey::default());
+    let proof = Proof::try_from(&VALID_PROOF_DATA).unwrap();
+    let pubs = Public::from(VALID_PUBS_DATA);
+
+    bencher.bench_local(|| {
+        verify_prepared(
+            divan::black_box(&vk),
+            divan::black_box(&proof),
+            divan::black_box(&pubs),
+        )
+        .is_ok()
+    });
+}
+
+#[divan::bench(args = [1, 4, 16])]
+fn fflonk_verifier_batch_with_base_key(n: usize) -> bool {
+    fn compute(vk: VerificationKey, batch: Vec<(Proof, Public)>) -> bool {
+        verify_batch(&vk, &batch).is_ok()
+    }
+
+    let vk = VerificationKey::default();
+    let proof = Proof::try_from(&VALID_PROOF_DATA).unwrap();
+    let batch = vec![(proof, Public::from(VALID_PUBS_DATA)); n];
+
+    compute(divan::black_box(vk), divan::black_box(batch))
+}
+
+#[divan::bench(args = [1, 4, 16])]
+fn fflonk_verifier_many_with_base_key(bencher: divan::Bencher, n: usize) {
+    let vk = VerificationKey::default();
+    let proof = Proof::try_from(&VALID_PROOF_DATA).unwrap();
+    let pubs = Public::from(VALID_PUBS_DATA);
+    let items = vec![(&vk, &proof, &pubs); n];
+
+    bencher.bench_local(|| verify_many(divan::black_box(&items)).is_ok());
+}
@@ -0,0 +1,153 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A `dudect`-style timing leakage test of the verifier's scalar path: time the same
+//! workload on a fixed input and on random inputs, in a random order, and check with the
+//! Welch's t-test that the two classes have the same timing distribution. A `|t|` over
+//! [`THRESHOLD`] is a leakage evidence: the `substrate-bn` scalars are there as reference
+//! and they should leak, the `CtBn254` ones should not.
+//!
+//! Run it in release mode with `cargo bench --features constant-time --bench constant_time`
+//! and set the `DUDECT_SAMPLES` environment variable to change the samples (per class).
+
+// Benchmarks need a toolchain newer than the crate's MSRV anyway (`std::hint::black_box`).
+#![allow(clippy::incompatible_msrv)]
+
+use std::{hint::black_box, process::ExitCode, time::Instant};
+
+use fflonk_verifier::{Bn254, CtBn254, Curve, Field};
+use rand::Rng;
+
+/// The `dudect` threshold on `|t|` to consider the classes distinguishable.
+const THRESHOLD: f64 = 4.5;
+/// The measurements over this percentile are dropped as noise.
+const PERCENTILE: f64 = 0.9;
+const DEFAULT_SAMPLES: usize = 20_000;
+/// The domain size power used to evaluate the vanishing and the Lagrange polynomials.
+const POWER: u64 = 16;
+
+/// The scalar work that the verifier does on a value derived from the public inputs: the
+/// vanishing polynomial, the Lagrange evaluations with their inverses and a Horner's
+/// polynomial evaluation.
+fn workload<F: Field>(x: F) -> F {
+    let n = F::from_u64(1 << POWER);
+    let zh = x.pow(1 << POWER) - F::one();
+    let w = F::from_u64(7);
+    let mut root = F::one();
+    let mut acc = F::zero();
+    for _ in 0..4 {
+        let den = (n * (x - root)).inverse().unwrap_or(F::zero());
+        acc = acc * x + zh * root * den;
+        root = root * w;
+    }
+    acc
+}
+
+/// The two classes of inputs: `fixed` is a small value (the worst case for a binary GCD
+/// inversion) and `random` a uniform one.
+fn class_input<F: Field>(random: bool, rng: &mut impl Rng) -> F {
+    if random {
+        F::from_be_bytes_reduced(&rng.gen())
+    } else {
+        F::from_u64(3)
+    }
+}
+
+/// Welch's t statistic of the two classes' measurements.
+fn welch_t(fixed: &[f64], random: &[f64]) -> f64 {
+    fn mean_var(values: &[f64]) -> (f64, f64) {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, var)
+    }
+    let (m0, v0) = mean_var(fixed);
+    let (m1, v1) = mean_var(random);
+    (m0 - m1) / (v0 / fixed.len() as f64 + v1 / random.len() as f64).sqrt()
+}
+
+/// Drop the measurements over the [`PERCENTILE`] of both classes.
+fn crop(fixed: &mut Vec<f64>, random: &mut Vec<f64>) {
+    let mut all = fixed
+        .iter()
+        .chain(random.iter())
+        .copied()
+        .collect::<Vec<_>>();
+    all.sort_by(f64::total_cmp);
+    let limit = all[((all.len() - 1) as f64 * PERCENTILE) as usize];
+    fixed.retain(|&v| v <= limit);
+    random.retain(|&v| v <= limit);
+}
+
+/// Measure the [`workload`] on the `C` scalars and return the `t` statistic.
+fn measure<C: Curve>(samples: usize) -> f64 {
+    let mut rng = rand::thread_rng();
+    let (mut fixed, mut random) = (Vec::with_capacity(samples), Vec::with_capacity(samples));
+    while fixed.len() < samples || random.len() < samples {
+        let class = if fixed.len() == samples {
+            true
+        } else if random.len() == samples {
+            false
+        } else {
+            rng.gen()
+        };
+        // The input is prepared out of the measurement.
+        let input = black_box(class_input::<C::Fr>(class, &mut rng));
+        let start = Instant::now();
+        black_box(workload(input));
+        let elapsed = start.elapsed().as_nanos() as f64;
+        if class {
+            random.push(elapsed);
+        } else {
+            fixed.push(elapsed);
+        }
+    }
+    crop(&mut fixed, &mut random);
+    welch_t(&fixed, &random)
+}
+
+fn main() -> ExitCode {
+    let samples = std::env::var("DUDECT_SAMPLES")
+        .ok()
+        .and_then(|samples| samples.parse().ok())
+        .unwrap_or(DEFAULT_SAMPLES);
+    // Warm up the caches and the CPU frequency.
+    measure::<CtBn254>(samples / 10);
+
+    let reference = measure::<Bn254>(samples);
+    let constant_time = measure::<CtBn254>(samples);
+    let verdict = |t: f64| {
+        if t.abs() > THRESHOLD {
+            "leakage"
+        } else {
+            "no leakage evidence"
+        }
+    };
+    println!("samples per class: {samples}, threshold |t| > {THRESHOLD}");
+    println!(
+        "Bn254   (substrate-bn): t = {reference:>10.2} -> {}",
+        verdict(reference)
+    );
+    println!(
+        "CtBn254 (constant-time): t = {constant_time:>10.2} -> {}",
+        verdict(constant_time)
+    );
+    if constant_time.abs() > THRESHOLD {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi-header")]
+    ffi_header();
+    #[cfg(feature = "ffi-tests")]
+    ffi_harness();
+}
+
+#[cfg(feature = "ffi-header")]
+fn include_dir() -> std::path::PathBuf {
+    let out_dir = std::env::var("OUT_DIR").expect("Cargo should set OUT_DIR");
+    std::path::Path::new(&out_dir).join("include")
+}
+
+/// Generate the C header in `OUT_DIR`: the tests check that it's the committed
+/// `include/fflonk_verifier.h`.
+#[cfg(feature = "ffi-header")]
+fn ffi_header() {
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+
+    let include = include_dir();
+    let config = cbindgen::Config::from_file("cbindgen.toml").expect("Cannot read cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("src/ffi.rs")
+        .generate()
+        .expect("Cannot generate the C header")
+        .write_to_file(include.join("fflonk_verifier.h"));
+}
+
+/// Build the C test harness on the generated header: it's linked only in the tests.
+#[cfg(feature = "ffi-tests")]
+fn ffi_harness() {
+    println!("cargo:rerun-if-changed=tests/ffi/harness.c");
+
+    let include = include_dir();
+    cc::Build::new()
+        .file("tests/ffi/harness.c")
+        .include(&include)
+        .warnings_into_errors(true)
+        .cargo_metadata(false)
+        .compile("fflonk_harness");
+    println!(
+        "cargo:rustc-link-arg-tests={}",
+        include.with_file_name("libfflonk_harness.a").display()
+    );
+}
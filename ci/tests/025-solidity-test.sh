@@ -0,0 +1,16 @@
+#!/bin/bash
+# shellcheck disable=SC2086
+set -eo pipefail
+
+cd "${RUST_SUBFOLDER}" || exit
+
+SOLC_VERSION="${SOLC_VERSION:-0.8.26}"
+solc_dir="$(mktemp -d)"
+curl -sSfL -o "${solc_dir}/solc" \
+  "https://github.com/ethereum/solidity/releases/download/v${SOLC_VERSION}/solc-static-linux"
+chmod +x "${solc_dir}/solc"
+export PATH="${solc_dir}:${PATH}"
+
+# Compile the generated Solidity verifier and run it in revm
+echo "" && echo "=== Running the solidity verifier tests ===" && echo ""
+cargo $CARGOARGS test --all-features --release --test solidity -- --ignored
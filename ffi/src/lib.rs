@@ -0,0 +1,20 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `libfflonk_verifier_ffi.a` and `libfflonk_verifier_ffi.so` C libraries: they export the
+//! `fflonk_verifier` C ABI (its `ffi` feature) declared in `include/fflonk_verifier.h`.
+
+pub use fflonk_verifier::ffi::*;
@@ -0,0 +1,103 @@
+/*
+ * Copyright 2024, Horizen Labs, Inc.
+ *
+ * fflonk_verifier is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fflonk_verifier is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+#ifndef FFLONK_VERIFIER_H
+#define FFLONK_VERIFIER_H
+
+/* Generated by cbindgen from src/ffi.rs with the `ffi` feature: don't edit it by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The proof is valid.
+#define FFLONK_OK 0
+
+// A null pointer is given for a non empty buffer.
+#define FFLONK_ERROR_NULL_POINTER -1
+
+// The verification key bytes are not a valid key.
+#define FFLONK_ERROR_INVALID_KEY_DATA -2
+
+// A proof's evaluation or commitment coordinate is not a field element.
+#define FFLONK_ERROR_PROOF_INVALID_FIELD -3
+
+// A proof's commitment is not a G1 point.
+#define FFLONK_ERROR_PROOF_INVALID_POINT -4
+
+// The proof bytes are not 768.
+#define FFLONK_ERROR_PROOF_WRONG_LENGTH -5
+
+// The public inputs bytes are not a multiple of 32.
+#define FFLONK_ERROR_PUBLIC_INPUTS_WRONG_LENGTH -6
+
+// The proof's inverse is wrong.
+#define FFLONK_ERROR_INVALID_INVERSE -7
+
+// The number of public inputs doesn't match the verification key one.
+#define FFLONK_ERROR_INVALID_PUBLIC_INPUTS_NUMBER -8
+
+// A public input is not a scalar field element.
+#define FFLONK_ERROR_INVALID_PUBLIC_INPUT -9
+
+// A proof's commitment is the point at infinity.
+#define FFLONK_ERROR_POINT_AT_INFINITY -10
+
+// A challenge makes the verifier equations degenerate.
+#define FFLONK_ERROR_DEGENERATE_CHALLENGE -11
+
+// The verification key is malformed.
+#define FFLONK_ERROR_INVALID_VERIFICATION_KEY -12
+
+// The pairing check failed: the proof is not valid.
+#define FFLONK_ERROR_NOT_PAIRING -13
+
+// The verifier panicked: it's a bug, please report it.
+#define FFLONK_ERROR_PANIC -14
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Verify the BN254 proof against the public inputs:
+// - `vk` is the 480 bytes key encoding (`VerificationKey::to_bytes()`),
+// - `proof` is the 768 bytes proof (24 big-endian words),
+// - `pubs` is the concatenation of the 32 bytes big-endian public inputs.
+//
+// Return `FFLONK_OK` if the proof is valid or one of the `FFLONK_ERROR_*` codes.
+//
+// # Safety
+//
+// Every pointer should be valid to read the given number of bytes or, if the length is 0,
+// can be null.
+int32_t fflonk_verify(const uint8_t *vk_ptr,
+                      size_t vk_len,
+                      const uint8_t *proof_ptr,
+                      size_t proof_len,
+                      const uint8_t *pubs_ptr,
+                      size_t pubs_len);
+
+// Return the description of the given result code as a static NUL terminated string.
+const char *fflonk_error_message(int32_t code);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* FFLONK_VERIFIER_H */
@@ -0,0 +1,121 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The deferred pairing check: the verifier's scalar work gives the two G1 points of the
+//! pairing equation `e(lhs, G2) = e(rhs, X_2)`, that can be accumulated by random linear
+//! combination and checked once at the end (or proven inside another circuit).
+
+use snafu::Snafu;
+
+use crate::{
+    batch::{batch_scalars, encoded},
+    curve::{Bn254, Curve, Point},
+    Keccak256Transcript, PreparedVerificationKey, Proof, Public, VerifyError,
+};
+
+/// Pairing Accumulator Error
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum PairingAccumulatorError {
+    /// The accumulators pair with the `X_2` of different verification keys
+    #[snafu(display("Cannot accumulate the pairings of different verification keys"))]
+    DifferentKeys,
+}
+
+/// The pairing equation `e(lhs, G2) = e(rhs, X_2)` of one or more proofs of the same
+/// verification key, where `G2` is [`PairingAccumulator::g2_pair`] and `X_2` is the key's
+/// one. Build it by [`verify_deferred`], merge it with the other proofs' ones by
+/// [`PairingAccumulator::accumulate`] and do the pairing check by
+/// [`PairingAccumulator::finalize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingAccumulator<C: Curve = Bn254> {
+    lhs: C::G1,
+    rhs: C::G1,
+    x2: C::G2,
+}
+
+/// Like [`crate::verify_prepared`] but don't do the pairing check: return the
+/// [`PairingAccumulator`] to check later. Can fail for the same reasons of
+/// [`crate::verify_prepared`] but the pairing one.
+pub fn verify_deferred<C: Curve>(
+    vk: &PreparedVerificationKey<C>,
+    proof: &Proof<C>,
+    pubs: &Public,
+) -> Result<PairingAccumulator<C>, VerifyError> {
+    let (challenges, (f, e, j)) =
+        proof.compute_challenges_and_fej::<Keccak256Transcript<C>>(vk, pubs)?;
+    Ok(PairingAccumulator {
+        lhs: proof.compute_pairing_lhs(&challenges, f, e, j),
+        rhs: proof.polynomials.w2,
+        x2: vk.precomputed.x2,
+    })
+}
+
+impl<C: Curve> PairingAccumulator<C> {
+    /// An empty accumulator for the `vk` proofs: it holds trivially.
+    pub fn new(vk: &PreparedVerificationKey<C>) -> Self {
+        Self {
+            lhs: C::G1::zero(),
+            rhs: C::G1::zero(),
+            x2: vk.precomputed.x2,
+        }
+    }
+
+    /// The G1 point paired with [`PairingAccumulator::g2_pair`].
+    pub fn lhs(&self) -> C::G1 {
+        self.lhs
+    }
+
+    /// The G1 point paired with [`PairingAccumulator::x2`].
+    pub fn rhs(&self) -> C::G1 {
+        self.rhs
+    }
+
+    /// The G2 generator.
+    pub fn g2_pair() -> C::G2 {
+        C::G2::generator()
+    }
+
+    /// The verification key's `X_2`.
+    pub fn x2(&self) -> C::G2 {
+        self.x2
+    }
+
+    /// Merge the `other` pairing equation in this one by a random linear combination, whose
+    /// scalars are derived by hashing both the equations. Fails if the accumulators are
+    /// for different verification keys.
+    pub fn accumulate(&mut self, other: &Self) -> Result<(), PairingAccumulatorError> {
+        if self.x2 != other.x2 {
+            return Err(PairingAccumulatorError::DifferentKeys);
+        }
+        let x2 = encoded(&self.x2);
+        let scalars = batch_scalars(
+            &[(self.lhs, self.rhs), (other.lhs, other.rhs)],
+            &[x2.as_slice(); 2],
+        );
+        self.lhs = self.lhs * scalars[0] + other.lhs * scalars[1];
+        self.rhs = self.rhs * scalars[0] + other.rhs * scalars[1];
+        Ok(())
+    }
+
+    /// Do the pairing check of all the accumulated proofs.
+    pub fn finalize(&self) -> Result<(), VerifyError> {
+        if C::pairing_check(&[(self.lhs, Self::g2_pair()), (-self.rhs, self.x2)]) {
+            Ok(())
+        } else {
+            Err(VerifyError::NotPairing)
+        }
+    }
+}
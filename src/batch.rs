@@ -0,0 +1,208 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use snafu::Snafu;
+use substrate_bn::arith::U256;
+
+use crate::{
+    check_paring,
+    curve::{Curve, Field, MaybeSendSync, Point},
+    hash::Hasher as _,
+    utils::IntoU256 as _,
+    Keccak256Transcript, PreparedVerificationKey, Proof, Public, VerificationKey, VerifyError,
+};
+
+/// Batch Verification Error
+#[derive(Snafu, Debug)]
+pub enum BatchVerifyError {
+    /// The proof at the given position in the batch is not valid
+    #[snafu(display("Invalid proof at index {index}: {error}"))]
+    InvalidProof { index: usize, error: VerifyError },
+    /// Cannot verify the batched pairing even if every single proof is valid
+    #[snafu(display("Cannot verify batched paring"))]
+    NotPairing,
+}
+
+/// Use the given verification key `vk` to verify all the `proofs` against their public inputs.
+/// The pairing checks of all proofs are combined, by random linear combination, in a single
+/// pairing check. If it fails, every proof is checked on its own to find the invalid one.
+/// Can fail if:
+/// - the verification key is malformed: reported on the first proof
+/// - the number of public inputs of some proof is not the one declared in the key
+/// - some public input, commitment or challenge of some proof is not valid
+/// - the provided inverse in some proof is wrong
+/// - the pair checking of some proof is wrong
+pub fn verify_batch<C: Curve>(
+    vk: &VerificationKey<C>,
+    proofs: &[(Proof<C>, Public)],
+) -> Result<(), BatchVerifyError> {
+    let vk = PreparedVerificationKey::from(vk);
+    let terms = compute_terms(proofs.len(), |index| {
+        let (proof, pubs) = &proofs[index];
+        pairing_terms(&vk, proof, pubs)
+    })?;
+    if terms.is_empty() {
+        return Ok(());
+    }
+
+    let x2 = encoded(&vk.precomputed.x2);
+    let (lhs, w2) = batch_scalars(&terms, &alloc::vec![x2.as_slice(); terms.len()])
+        .into_iter()
+        .zip(terms.iter())
+        .fold(
+            (C::G1::zero(), C::G1::zero()),
+            |(acc_lhs, acc_w2), (r, (lhs, w2))| (acc_lhs + *lhs * r, acc_w2 + *w2 * r),
+        );
+    if check_paring(lhs, w2, &vk).is_ok() {
+        return Ok(());
+    }
+
+    for (index, (lhs, w2)) in terms.into_iter().enumerate() {
+        check_paring(lhs, w2, &vk)
+            .map_err(|error| BatchVerifyError::InvalidProof { index, error })?;
+    }
+    Err(BatchVerifyError::NotPairing)
+}
+
+/// Verify the proofs of many verification keys: every item is a key, a proof and its
+/// public inputs. Like [`verify_batch`], the pairing checks of all proofs are combined in a
+/// single pairing check, but the `X_2` terms of the proofs for keys with the same `X_2` (the
+/// keys from the same SRS) are summed up: the pairings are one more than the distinct `X_2`,
+/// whatever the number of proofs is. If it fails, every proof is checked on its own to find
+/// the invalid one.
+/// Can fail for the same reasons of [`verify_batch`]: the errors report the item's index.
+pub fn verify_many<C: Curve>(
+    items: &[(&VerificationKey<C>, &Proof<C>, &Public)],
+) -> Result<(), BatchVerifyError> {
+    let mut keys: Vec<PreparedVerificationKey<C>> = Vec::new();
+    let mut ids = BTreeMap::new();
+    let key_indexes = items
+        .iter()
+        .map(|(vk, _, _)| {
+            *ids.entry(vk.hash()).or_insert_with(|| {
+                keys.push(PreparedVerificationKey::from(*vk));
+                keys.len() - 1
+            })
+        })
+        .collect::<Vec<_>>();
+    let terms = compute_terms(items.len(), |index| {
+        let (_, proof, pubs) = items[index];
+        pairing_terms(&keys[key_indexes[index]], proof, pubs)
+    })?;
+    if terms.is_empty() {
+        return Ok(());
+    }
+
+    let x2s = keys
+        .iter()
+        .map(|vk| encoded(&vk.precomputed.x2))
+        .collect::<Vec<_>>();
+    let mut pairs = Vec::new();
+    let mut x2_indexes = BTreeMap::new();
+    let key_pairs = keys
+        .iter()
+        .zip(x2s.iter())
+        .map(|(vk, x2)| {
+            *x2_indexes.entry(x2.as_slice()).or_insert_with(|| {
+                pairs.push((C::G1::zero(), &vk.precomputed.x2_prepared));
+                pairs.len() - 1
+            })
+        })
+        .collect::<Vec<_>>();
+    let term_x2s = key_indexes
+        .iter()
+        .map(|key| x2s[*key].as_slice())
+        .collect::<Vec<_>>();
+    let mut lhs = C::G1::zero();
+    for ((r, (term_lhs, w2)), key) in batch_scalars(&terms, &term_x2s)
+        .into_iter()
+        .zip(terms.iter())
+        .zip(key_indexes.iter())
+    {
+        lhs = lhs + *term_lhs * r;
+        let pair = &mut pairs[key_pairs[*key]];
+        pair.0 = pair.0 - *w2 * r;
+    }
+    pairs.push((lhs, &keys[0].precomputed.g2_prepared));
+    if C::prepared_pairing_check(&pairs) {
+        return Ok(());
+    }
+
+    for (index, ((lhs, w2), key)) in terms.into_iter().zip(key_indexes).enumerate() {
+        check_paring(lhs, w2, &keys[key])
+            .map_err(|error| BatchVerifyError::InvalidProof { index, error })?;
+    }
+    Err(BatchVerifyError::NotPairing)
+}
+
+/// Compute the pairing terms `(lhs, W2)` of a proof.
+fn pairing_terms<C: Curve>(
+    vk: &PreparedVerificationKey<C>,
+    proof: &Proof<C>,
+    pubs: &Public,
+) -> Result<(C::G1, C::G1), VerifyError> {
+    proof
+        .compute_challenges_and_fej::<Keccak256Transcript<C>>(vk, pubs)
+        .map(|(challenges, (f, e, j))| {
+            (
+                proof.compute_pairing_lhs(&challenges, f, e, j),
+                proof.polynomials.w2,
+            )
+        })
+}
+
+/// Compute the pairing terms of the proofs from `0` to `len` by `terms`, on the rayon thread
+/// pool with the `parallel` feature: the error is the one of the first invalid proof anyway.
+fn compute_terms<T, F>(len: usize, terms: F) -> Result<Vec<T>, BatchVerifyError>
+where
+    T: MaybeSendSync,
+    F: Fn(usize) -> Result<T, VerifyError> + MaybeSendSync,
+{
+    let compute =
+        |index| terms(index).map_err(|error| BatchVerifyError::InvalidProof { index, error });
+    #[cfg(feature = "parallel")]
+    let terms = {
+        use rayon::prelude::*;
+
+        (0..len).into_par_iter().map(compute).collect::<Vec<_>>()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let terms = (0..len).map(compute);
+    terms.into_iter().collect()
+}
+
+/// Derive the linear combination scalars by hashing all the pairing inputs, the `(lhs, W2)`
+/// terms and the encoded `X_2` of their keys (`x2s`, one for each term): they cannot be known
+/// before the proofs and the keys are fixed.
+pub(crate) fn batch_scalars<P: Point>(terms: &[(P, P)], x2s: &[&[u8]]) -> Vec<P::Scalar> {
+    let mut data = Vec::new();
+    for ((lhs, w2), x2) in terms.iter().zip(x2s) {
+        data.extend(encoded(lhs));
+        data.extend(encoded(w2));
+        data.extend_from_slice(x2);
+    }
+    let seed = data.hash().into_u256();
+    (0..terms.len())
+        .map(|i| P::Scalar::from_be_bytes_reduced(&[seed, U256::from(i as u64)].hash()))
+        .collect()
+}
+
+pub(crate) fn encoded<P: Point>(point: &P) -> Vec<u8> {
+    let mut out = alloc::vec![0_u8; P::ENCODED_LEN];
+    point.encode(&mut out);
+    out
+}
@@ -16,7 +16,8 @@
 
 use anyhow::{anyhow, Context, Result};
 use clap::ValueEnum;
-use fflonk_verifier::{Proof, ProofRawData};
+use fflonk_verifier::{Proof, ProofRawData, Public};
+use substrate_bn::arith::U256;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Format {
@@ -26,31 +27,53 @@ pub enum Format {
     Bytes,
     /// Hex String
     HexString,
+    /// Hex String of the `verifyProof` EVM ABI calldata (proof and public inputs)
+    Calldata,
 }
 
 impl Format {
-    pub fn read_poof(self, data: &[u8]) -> Result<Proof> {
+    /// Read the proof and, if the format contains them, the public inputs.
+    pub fn read_proof_and_pubs(self, data: &[u8]) -> Result<(Proof, Option<Public>)> {
         match self {
             Format::Json => serde_json::from_slice(data)
-                .with_context(|| format!("Failed to read proof from json")),
-            Format::Bytes => {
-                proof_from_slice(data).with_context(|| format!("Failed to read proof from bytes"))
-            }
-            Format::HexString => {
-                let data = if data.starts_with(b"0x") {
-                    &data[2..]
-                } else {
-                    data
-                };
-                hex::decode(data)
-                    .with_context(|| format!("Invalid hex string"))
-                    .and_then(|bytes| proof_from_slice(&bytes))
-                    .with_context(|| format!("Failed to read proof from hex"))
-            }
+                .context("Failed to read proof from json")
+                .map(|proof| (proof, None)),
+            Format::Bytes => proof_from_slice(data)
+                .context("Failed to read proof from bytes")
+                .map(|proof| (proof, None)),
+            Format::HexString => decode_hex(data)
+                .and_then(|bytes| proof_from_slice(&bytes))
+                .context("Failed to read proof from hex")
+                .map(|proof| (proof, None)),
+            Format::Calldata => decode_hex(data)
+                .and_then(|bytes| {
+                    Proof::decode_calldata(&bytes).context("Invalid verifyProof calldata")
+                })
+                .context("Failed to read proof from calldata")
+                .map(|(proof, pubs)| (proof, Some(pubs))),
         }
     }
 }
 
+pub fn parse_pubs(pubs: &[String]) -> Result<Public> {
+    pubs.iter()
+        .map(|pub_str| {
+            ethnum::U256::from_str_prefixed(pub_str)
+                .with_context(|| format!("Invalid 256 string: {pub_str}"))
+                .map(|u256| U256([u256.0[0], u256.0[1]]))
+        })
+        .collect()
+}
+
+fn decode_hex(data: &[u8]) -> Result<Vec<u8>> {
+    let data = if data.starts_with(b"0x") {
+        &data[2..]
+    } else {
+        data
+    };
+    hex::decode(data).context("Invalid hex string")
+}
+
 fn proof_from_slice(input: &[u8]) -> Result<Proof> {
     ProofRawData::try_from(input)
         .map_err(|_| {
@@ -59,7 +82,5 @@ fn proof_from_slice(input: &[u8]) -> Result<Proof> {
                 std::mem::size_of::<ProofRawData>(),
             )
         })
-        .and_then(|data| {
-            Proof::try_from(&data).with_context(|| format!("Failed to read proof from bytes"))
-        })
+        .and_then(|data| Proof::try_from(&data).context("Failed to read proof from bytes"))
 }
@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
 
+// The binaries need a toolchain newer than the crate's MSRV anyway (`clap` requirement).
+#![allow(clippy::incompatible_msrv)]
+
 use anyhow::{Context, Result};
 use clap::Parser;
 use fflonk_verifier::{Proof, ProofRawData};
@@ -48,26 +51,39 @@ struct Cli {
 
     /// Output file [or stdout if not specified]
     output: Option<std::path::PathBuf>,
+
+    /// Calldata output public inputs hex or decimal strings [or the input calldata ones if not
+    /// specified]
+    #[arg(long = "pub", value_name = "PUB")]
+    pubs: Vec<String>,
 }
 
 mod formats;
 
 pub fn main() -> Result<()> {
     let cli = Cli::parse();
-    let proof = std::fs::read(&cli.input)
+    let (proof, in_pubs) = std::fs::read(&cli.input)
         .with_context(|| format!("Failed to read proof data from {:?}", &cli.input))
-        .and_then(|data| cli.in_fmt.read_poof(data.as_slice()))
+        .and_then(|data| cli.in_fmt.read_proof_and_pubs(data.as_slice()))
         .with_context(|| format!("Failed to read proof from file {:?}", &cli.input))?;
+    let pubs = match in_pubs {
+        Some(in_pubs) if cli.pubs.is_empty() => in_pubs,
+        _ => formats::parse_pubs(&cli.pubs)?,
+    };
     let mut out = out_file(cli.output.as_ref())?;
     match cli.out_fmt {
-        formats::Format::Json => serde_json::to_writer_pretty(out, &proof)
-            .with_context(|| format!("Cannot serialize proof to json"))?,
+        formats::Format::Json => {
+            serde_json::to_writer_pretty(out, &proof).context("Cannot serialize proof to json")?
+        }
         formats::Format::Bytes => out
             .write_all(&ProofRawData::from(proof))
-            .with_context(|| format!("Cannot serialize proof to bytes"))?,
+            .context("Cannot serialize proof to bytes")?,
         formats::Format::HexString => out
             .write_all(render_proof_hex(proof).as_bytes())
-            .with_context(|| format!("Cannot serialize proof to hex string"))?,
+            .context("Cannot serialize proof to hex string")?,
+        formats::Format::Calldata => out
+            .write_all(format!("0x{}", hex::encode(proof.encode_calldata(&pubs))).as_bytes())
+            .context("Cannot serialize proof to calldata")?,
     }
 
     Ok(())
@@ -76,7 +92,7 @@ pub fn main() -> Result<()> {
 fn out_file(output: Option<&std::path::PathBuf>) -> Result<Box<dyn std::io::Write>> {
     let from_path = output
         .map(|p| {
-            std::fs::File::create(&p)
+            std::fs::File::create(p)
                 .with_context(|| format!("Failed to create output file {:?}", &p))
         })
         .transpose()?
@@ -14,16 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
 
+// The binaries need a toolchain newer than the crate's MSRV anyway (`clap` requirement).
+#![allow(clippy::incompatible_msrv)]
+
 use anyhow::{Context, Result};
-use clap::Parser;
-use fflonk_verifier::{verify, Public, VerificationKey};
-use substrate_bn::arith::U256;
+use clap::{Parser, Subcommand};
+use fflonk_verifier::{solidity_verifier, verify, verify_with_trace, VerificationKey};
 
 #[derive(Parser, Debug)]
 #[command(name = "verifier")]
 #[command(about = "Verify fflonk-proofs")]
 #[command(version)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// Proof format
     #[arg(
         short,
@@ -34,37 +40,139 @@ struct Cli {
     )]
     proof_fmt: formats::Format,
 
+    /// Dump the verification intermediate values as json on stdout
+    #[arg(long)]
+    trace: bool,
+
     /// Verification Key Json File
-    vk: std::path::PathBuf,
+    #[arg(required = true)]
+    vk: Option<std::path::PathBuf>,
 
     /// Proof File
-    proof: std::path::PathBuf,
+    #[arg(required = true)]
+    proof: Option<std::path::PathBuf>,
 
-    /// Public input hex string
-    pubs: String,
+    /// Public inputs hex or decimal strings [or the calldata ones if not specified]
+    pubs: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Export the verification key from a snarkjs fflonk zkey file to json
+    VkExport {
+        /// Zkey File
+        zkey: std::path::PathBuf,
+
+        /// Output file [or stdout if not specified]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Check that a json verification key is consistent and print its identifier
+    CheckVk {
+        /// Verification Key Json File
+        vk: std::path::PathBuf,
+    },
+    /// Generate the Solidity verifier contract for a json verification key
+    SolidityExport {
+        /// Verification Key Json File
+        vk: std::path::PathBuf,
+
+        /// Output file [or stdout if not specified]
+        output: Option<std::path::PathBuf>,
+    },
 }
 
 mod formats;
 
 pub fn main() -> Result<()> {
     let cli = Cli::parse();
-    let proof = std::fs::read(&cli.proof)
-        .with_context(|| format!("Failed to read proof data from {:?}", &cli.proof))
-        .and_then(|data| cli.proof_fmt.read_poof(data.as_slice()))
-        .with_context(|| format!("Failed to read proof from file {:?}", &cli.proof))?;
-    let vk: VerificationKey = serde_json::from_reader(
-        std::fs::File::open(&cli.vk)
-            .with_context(|| format!("Failed to open verification key file {:?}", &cli.vk))?,
-    )
-    .with_context(|| format!("Failed to deserialize verification key from {:?}", &cli.vk))?;
-    let pubs = parse_pubs(&cli.pubs)?;
-    verify(&vk, &proof, &pubs).with_context(|| format!("Failed to verify proof"))?;
-    println!("Proof verified successfully");
+    match cli.command {
+        Some(Commands::VkExport { zkey, output }) => vk_export(&zkey, output.as_ref()),
+        Some(Commands::CheckVk { vk }) => check_vk(&vk),
+        Some(Commands::SolidityExport { vk, output }) => solidity_export(&vk, output.as_ref()),
+        None => verify_proof(
+            cli.proof_fmt,
+            cli.vk.as_ref().expect("Required by clap"),
+            cli.proof.as_ref().expect("Required by clap"),
+            &cli.pubs,
+            cli.trace,
+        ),
+    }
+}
+
+fn verify_proof(
+    proof_fmt: formats::Format,
+    vk_path: &std::path::PathBuf,
+    proof_path: &std::path::PathBuf,
+    pubs: &[String],
+    trace: bool,
+) -> Result<()> {
+    let (proof, proof_pubs) = std::fs::read(proof_path)
+        .with_context(|| format!("Failed to read proof data from {:?}", proof_path))
+        .and_then(|data| proof_fmt.read_proof_and_pubs(data.as_slice()))
+        .with_context(|| format!("Failed to read proof from file {:?}", proof_path))?;
+    let vk = read_vk(vk_path)?;
+    let pubs = match proof_pubs {
+        Some(proof_pubs) if pubs.is_empty() => proof_pubs,
+        _ => formats::parse_pubs(pubs)?,
+    };
+    if trace {
+        let (result, trace) = verify_with_trace(&vk, &proof, &pubs);
+        if let Some(trace) = trace {
+            serde_json::to_writer_pretty(std::io::stdout(), &trace)
+                .context("Cannot serialize verification trace to json")?;
+            println!();
+        }
+        result.context("Failed to verify proof")?;
+    } else {
+        verify(&vk, &proof, &pubs).context("Failed to verify proof")?;
+        println!("Proof verified successfully");
+    }
     Ok(())
 }
 
-fn parse_pubs(pubs: &str) -> Result<Public> {
-    ethnum::U256::from_str_prefixed(pubs)
-        .with_context(|| format!("Invalid 256 string"))
-        .map(|u256| Public::from(U256([u256.0[0], u256.0[1]])))
+fn vk_export(zkey: &std::path::PathBuf, output: Option<&std::path::PathBuf>) -> Result<()> {
+    let vk = std::fs::read(zkey)
+        .with_context(|| format!("Failed to read zkey data from {:?}", zkey))
+        .and_then(|data| {
+            VerificationKey::from_zkey(&data)
+                .with_context(|| format!("Failed to read verification key from zkey {:?}", zkey))
+        })?;
+    serde_json::to_writer_pretty(out_file(output)?, &vk)
+        .context("Cannot serialize verification key to json")
+}
+
+fn check_vk(vk_path: &std::path::PathBuf) -> Result<()> {
+    let vk = read_vk(vk_path)?;
+    vk.validate()
+        .with_context(|| format!("Invalid verification key {:?}", vk_path))?;
+    println!("Verification key is valid: {}", vk.hash());
+    Ok(())
+}
+
+fn solidity_export(
+    vk_path: &std::path::PathBuf,
+    output: Option<&std::path::PathBuf>,
+) -> Result<()> {
+    let vk = read_vk(vk_path)?;
+    out_file(output)?
+        .write_all(solidity_verifier(&vk).as_bytes())
+        .context("Cannot write the solidity verifier")
+}
+
+fn read_vk(vk_path: &std::path::PathBuf) -> Result<VerificationKey> {
+    serde_json::from_reader(
+        std::fs::File::open(vk_path)
+            .with_context(|| format!("Failed to open verification key file {:?}", vk_path))?,
+    )
+    .with_context(|| format!("Failed to deserialize verification key from {:?}", vk_path))
+}
+
+fn out_file(output: Option<&std::path::PathBuf>) -> Result<Box<dyn std::io::Write>> {
+    Ok(match output {
+        Some(path) => Box::new(
+            std::fs::File::create(path)
+                .with_context(|| format!("Failed to create output file {:?}", path))?,
+        ),
+        None => Box::new(std::io::stdout()),
+    })
 }
@@ -0,0 +1,172 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The EVM ABI calldata of the fflonk contracts `verifyProof(bytes32[24],uint256[N])` call:
+//! the 4 bytes function selector followed by the proof words and the public inputs. Both the
+//! arrays have static size so they are encoded in place without any offset or length.
+
+use alloc::{format, string::String, vec::Vec};
+use snafu::Snafu;
+
+use crate::{hash::Hasher as _, proof::ProofDataError, Proof, ProofRawData, Public};
+
+const SELECTOR_LEN: usize = 4;
+const PROOF_LEN: usize = core::mem::size_of::<ProofRawData>();
+
+/// Calldata decoding error
+#[derive(Snafu, Debug)]
+pub enum CalldataError {
+    /// The calldata size doesn't fit the selector, the proof and whole public inputs
+    #[snafu(display("Invalid calldata length {len}"))]
+    InvalidLength { len: usize },
+    /// The selector is not the `verifyProof` one for the given public inputs number
+    #[snafu(display("Invalid selector {selector:?}: expected {expected:?}"))]
+    InvalidSelector {
+        selector: [u8; SELECTOR_LEN],
+        expected: [u8; SELECTOR_LEN],
+    },
+    /// The proof is not valid
+    #[snafu(display("Invalid calldata proof: {error}"))]
+    InvalidProof { error: ProofDataError },
+}
+
+/// The `verifyProof(bytes32[24],uint256[n_public])` function selector. Solidity doesn't
+/// support zero length arrays, so without public inputs it's the `verifyProof(bytes32[24])` one.
+pub fn verify_proof_selector(n_public: usize) -> [u8; SELECTOR_LEN] {
+    let signature = match n_public {
+        0 => String::from("verifyProof(bytes32[24])"),
+        n => format!("verifyProof(bytes32[24],uint256[{n}])"),
+    };
+    let hash = signature.as_bytes().hash();
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+impl Proof {
+    /// Encode the `verifyProof(bytes32[24],uint256[N])` calldata for this proof and the
+    /// given public inputs.
+    pub fn encode_calldata(&self, pubs: &Public) -> Vec<u8> {
+        let mut out = Vec::with_capacity(SELECTOR_LEN + PROOF_LEN + 32 * pubs.len());
+        out.extend_from_slice(&verify_proof_selector(pubs.len()));
+        out.extend_from_slice(&ProofRawData::from(self.clone()));
+        for value in pubs.as_slice() {
+            let mut word = [0_u8; 32];
+            value
+                .to_big_endian(&mut word)
+                .expect("Cannot fail: is a 32-byte array");
+            out.extend_from_slice(&word);
+        }
+        out
+    }
+
+    /// Decode the proof and the public inputs from a `verifyProof(bytes32[24],uint256[N])`
+    /// calldata: the number of public inputs is given by the calldata length and the selector
+    /// should match it.
+    pub fn decode_calldata(data: &[u8]) -> Result<(Self, Public), CalldataError> {
+        let len = data.len();
+        if len < SELECTOR_LEN + PROOF_LEN || (len - SELECTOR_LEN - PROOF_LEN) % 32 != 0 {
+            return Err(CalldataError::InvalidLength { len });
+        }
+        let (selector, data) = data.split_at(SELECTOR_LEN);
+        let (proof, pubs) = data.split_at(PROOF_LEN);
+        let selector: [u8; SELECTOR_LEN] = selector.try_into().expect("Cannot fail: 4 bytes");
+        let expected = verify_proof_selector(pubs.len() / 32);
+        if selector != expected {
+            return Err(CalldataError::InvalidSelector { selector, expected });
+        }
+        let proof: &ProofRawData = proof
+            .try_into()
+            .expect("Cannot fail: is a proof size slice");
+        let proof =
+            Proof::try_from(proof).map_err(|error| CalldataError::InvalidProof { error })?;
+        let pubs = Public::try_from(pubs).expect("Cannot fail: is a multiple of 32 bytes");
+        Ok((proof, pubs))
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::macros::u256;
+
+    const CALLDATA: &str = include_str!("../resources/bins/proof.calldata");
+
+    fn calldata() -> Vec<u8> {
+        hex::decode(CALLDATA.trim().trim_start_matches("0x")).unwrap()
+    }
+
+    #[rstest]
+    #[case::no_public_inputs(0, [0xdb, 0x99, 0x04, 0xd1])]
+    #[case::one_public_input(1, [0x91, 0x21, 0xda, 0x8a])]
+    #[case::two_public_inputs(2, [0x33, 0xa3, 0xae, 0xa2])]
+    fn compute_the_verify_proof_selector(#[case] n_public: usize, #[case] expected: [u8; 4]) {
+        assert_eq!(expected, verify_proof_selector(n_public));
+    }
+
+    #[test]
+    fn decode_the_calldata() {
+        let expected_proof =
+            Proof::try_from(&<ProofRawData>::try_from(&calldata()[4..772]).unwrap()).unwrap();
+
+        let (proof, pubs) = Proof::decode_calldata(&calldata()).unwrap();
+
+        assert_eq!(expected_proof, proof);
+        assert_eq!(
+            Public::from(u256!(
+                "110d778eaf8b8ef7ac10f8ac239a14df0eb292a8d1b71340d527b26301a9ab08"
+            )),
+            pubs
+        );
+    }
+
+    #[rstest]
+    fn encode_and_decode_calldata(#[values(0, 1, 3)] n_public: usize) {
+        let (proof, _) = Proof::decode_calldata(&calldata()).unwrap();
+        let pubs = (0..n_public as u64)
+            .map(substrate_bn::arith::U256::from)
+            .collect::<Public>();
+
+        let data = proof.encode_calldata(&pubs);
+
+        assert_eq!(4 + 768 + 32 * n_public, data.len());
+        assert_eq!((proof, pubs), Proof::decode_calldata(&data).unwrap());
+    }
+
+    #[test]
+    fn encode_the_calldata() {
+        let (proof, pubs) = Proof::decode_calldata(&calldata()).unwrap();
+
+        assert_eq!(calldata(), proof.encode_calldata(&pubs));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "InvalidLength")]
+    #[case::empty(Vec::new())]
+    #[should_panic(expected = "InvalidLength")]
+    #[case::truncated_proof(calldata()[..700].to_vec())]
+    #[should_panic(expected = "InvalidLength")]
+    #[case::truncated_public_input(calldata()[..800].to_vec())]
+    #[should_panic(expected = "InvalidSelector")]
+    #[case::wrong_selector({ let mut data = calldata(); data[0] ^= 1; data })]
+    #[should_panic(expected = "InvalidSelector")]
+    #[case::more_public_inputs({ let mut data = calldata(); data.extend([0; 32]); data })]
+    #[should_panic(expected = "InvalidProof")]
+    #[case::invalid_proof({ let mut data = calldata(); data[4 + 31] ^= 1; data })]
+    fn reject_invalid_calldata(#[case] data: Vec<u8>) {
+        Proof::decode_calldata(&data).unwrap();
+    }
+}
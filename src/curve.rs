@@ -0,0 +1,204 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The pairing-friendly curves supported by the verifier: the verification is generic over
+//! [`Curve`] and every curve provides its scalar field, its groups and the pairing check.
+//! [`Bn254`] is the snarkjs' `bn128` curve and the default one and [`ArkBn254`] (with the
+//! `arkworks` feature) is the `bn128` curve on the arkworks arithmetic. [`CtBn254`] (with the
+//! `constant-time` feature) is the `bn128` curve with a constant-time scalar field.
+
+use core::{
+    fmt::Debug,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+#[cfg(feature = "arkworks")]
+mod arkworks;
+mod bn254;
+#[cfg(feature = "constant-time")]
+mod constant_time;
+#[cfg(any(feature = "arkworks", feature = "constant-time"))]
+mod convert;
+
+#[cfg(feature = "arkworks")]
+pub use arkworks::ArkBn254;
+pub use bn254::Bn254;
+#[cfg(feature = "zkey")]
+pub(crate) use bn254::FR_MODULUS;
+#[cfg(feature = "constant-time")]
+pub use constant_time::{CtBn254, CtFr, CtG1, CtG2};
+
+/// A pairing-friendly curve: the marker type that selects the fields and the groups used to
+/// verify the proofs.
+pub trait Curve: Copy + Debug + Default + PartialEq + Eq + 'static {
+    /// The scalar field.
+    type Fr: Field;
+    /// The group of the commitments.
+    type G1: Point<Scalar = Self::Fr>;
+    /// The group of the verification key's `X_2`.
+    type G2: Point<Scalar = Self::Fr>;
+    /// A `G2` point with its Miller loop line coefficients precomputed, where the arithmetic
+    /// exposes them, or the point itself.
+    type G2Prepared: Clone + Debug + MaybeSendSync;
+
+    /// The snarkjs' curve name.
+    const NAME: &'static str;
+    /// The 2-adicity of `Fr`: the max domain size power.
+    const MAX_POWER: u8;
+
+    /// Return `true` if the product of the pairings `e(g1, g2)` is the identity.
+    fn pairing_check(pairs: &[(Self::G1, Self::G2)]) -> bool;
+
+    /// Precompute the Miller loop line coefficients of a `G2` point.
+    fn prepare(g2: &Self::G2) -> Self::G2Prepared;
+
+    /// Like [`Curve::pairing_check`] but on the prepared `G2` points.
+    fn prepared_pairing_check(pairs: &[(Self::G1, &Self::G2Prepared)]) -> bool;
+}
+
+/// With the `parallel` feature the fields and the points should be `Send + Sync`, to be
+/// shared by the rayon threads: without it there's no bound.
+#[cfg(feature = "parallel")]
+pub trait MaybeSendSync: Send + Sync {}
+#[cfg(feature = "parallel")]
+impl<T: Send + Sync> MaybeSendSync for T {}
+/// With the `parallel` feature the fields and the points should be `Send + Sync`, to be
+/// shared by the rayon threads: without it there's no bound.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSendSync {}
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSendSync for T {}
+
+/// A scalar field: all the supported fields fit in 32 bytes.
+pub trait Field:
+    Copy
+    + MaybeSendSync
+    + Debug
+    + PartialEq
+    + Eq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn from_u64(value: u64) -> Self;
+
+    fn is_zero(&self) -> bool;
+
+    /// The multiplicative inverse: `None` for zero.
+    fn inverse(self) -> Option<Self>;
+
+    /// Read a big-endian value: `None` if it's not less than the field modulus.
+    fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self>;
+
+    /// Read a big-endian value reduced modulo the field modulus.
+    fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Self;
+
+    /// The canonical big-endian representation.
+    fn to_be_bytes(&self) -> [u8; 32];
+
+    fn pow(self, exp: u64) -> Self {
+        (0..u64::BITS - exp.leading_zeros())
+            .rev()
+            .fold(Self::one(), |acc, bit| {
+                let acc = acc * acc;
+                if exp >> bit & 1 == 1 {
+                    acc * self
+                } else {
+                    acc
+                }
+            })
+    }
+}
+
+/// A group of curve points. The points are encoded by their affine coordinates as
+/// big-endian base field elements (`Fq2` elements as imaginary and real parts, like in
+/// EIP-197) and the point at infinity is encoded as all zeros.
+pub trait Point:
+    Copy
+    + MaybeSendSync
+    + Debug
+    + PartialEq
+    + Eq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Neg<Output = Self>
+    + Mul<Self::Scalar, Output = Self>
+{
+    type Scalar: Field;
+
+    /// The number of the base field elements in the encoding.
+    const COORDINATES: usize;
+    /// The size in bytes of a base field element.
+    const COORDINATE_LEN: usize;
+    /// The size in bytes of the encoding.
+    const ENCODED_LEN: usize = Self::COORDINATES * Self::COORDINATE_LEN;
+
+    fn zero() -> Self;
+
+    fn generator() -> Self;
+
+    fn is_zero(&self) -> bool;
+
+    /// Convert to affine coordinates, if the representation is a projective one, to speed up
+    /// the next operations.
+    fn normalize(&mut self) {}
+
+    /// Write the encoding in `out`: it should be [`Point::ENCODED_LEN`] bytes.
+    fn encode(&self, out: &mut [u8]);
+
+    /// Read a point from its [`Point::ENCODED_LEN`] bytes encoding: other lengths are a
+    /// [`PointError::InvalidLength`].
+    fn decode(data: &[u8]) -> Result<Self, PointError>;
+
+    /// Read a point from its Jacobian coordinates: `xy` are `x` and `y` encoded like in
+    /// [`Point::decode`] and `z` is encoded like them (half of [`Point::ENCODED_LEN`]
+    /// bytes). The affine point is `(x/z^2, y/z^3)` and `z = 0` is the point at infinity.
+    /// The default implementation supports just `z = 0` and `z = 1`.
+    fn decode_jacobian(xy: &[u8], z: &[u8]) -> Result<Self, PointError> {
+        match z.split_last() {
+            Some((0, high)) if high.iter().all(|b| *b == 0) => Ok(Self::zero()),
+            Some((1, high)) if high.iter().all(|b| *b == 0) => Self::decode(xy),
+            _ => Err(PointError::InvalidCoordinate {
+                index: Self::COORDINATES,
+            }),
+        }
+    }
+}
+
+/// Point decoding error
+#[derive(Debug)]
+pub enum PointError {
+    /// The data are not the encoding length
+    InvalidLength { len: usize, expected: usize },
+    /// The coordinate at the given position is not a base field element
+    InvalidCoordinate { index: usize },
+    /// The coordinates are not a point of the group
+    InvalidPoint { error: GroupError },
+}
+
+/// Why the coordinates are not a point of the group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupError {
+    /// The point is not on the curve
+    NotOnCurve,
+    /// The point is on the curve but not in the prime order subgroup
+    NotInSubgroup,
+}
@@ -0,0 +1,465 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The snarkjs' `bn128` curve on the arkworks `ark-bn254` arithmetic, with the lossless
+//! conversions between the [`Bn254`] and the [`ArkBn254`] proofs and keys.
+
+use ark_bn254::{g1, g2, Bn254 as Arkworks, Fq, Fq2, Fr};
+use ark_ec::{
+    pairing::Pairing,
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+    AffineRepr, CurveGroup, Group,
+};
+use ark_ff::{BigInt, Field as _, One, PrimeField, Zero};
+use substrate_bn::{arith::U256, AffineG1, AffineG2};
+
+use super::{
+    convert::{convert_key, convert_proof, Convert},
+    Bn254, Curve, Field, GroupError, Point, PointError,
+};
+use crate::{Proof, VerificationKey};
+
+// The `ark_bn254` aliases are projections of `BnConfig` and would make the `Point`
+// implementations overlap: use the concrete configurations.
+type G1Affine = Affine<g1::Config>;
+type G1Projective = Projective<g1::Config>;
+type G2Affine = Affine<g2::Config>;
+type G2Projective = Projective<g2::Config>;
+type G2Prepared = <Arkworks as Pairing>::G2Prepared;
+
+/// The BN254 (`bn128`) curve on the arkworks arithmetic: the scalars are `ark_bn254::Fr` and
+/// the points `ark_bn254::{G1Projective, G2Projective}`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArkBn254;
+
+impl Curve for ArkBn254 {
+    type Fr = Fr;
+    type G1 = G1Projective;
+    type G2 = G2Projective;
+    type G2Prepared = G2Prepared;
+
+    const NAME: &'static str = Bn254::NAME;
+    const MAX_POWER: u8 = Bn254::MAX_POWER;
+
+    fn pairing_check(pairs: &[(G1Projective, G2Projective)]) -> bool {
+        Arkworks::multi_pairing(
+            pairs.iter().map(|(g1, _)| g1.into_affine()),
+            pairs.iter().map(|(_, g2)| g2.into_affine()),
+        )
+        .is_zero()
+    }
+
+    fn prepare(g2: &G2Projective) -> G2Prepared {
+        g2.into_affine().into()
+    }
+
+    fn prepared_pairing_check(pairs: &[(G1Projective, &G2Prepared)]) -> bool {
+        Arkworks::multi_pairing(
+            pairs.iter().map(|(g1, _)| g1.into_affine()),
+            pairs.iter().map(|(_, g2)| (*g2).clone()),
+        )
+        .is_zero()
+    }
+}
+
+impl Field for Fr {
+    fn zero() -> Self {
+        <Fr as Zero>::zero()
+    }
+
+    fn one() -> Self {
+        <Fr as One>::one()
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Fr::from(value)
+    }
+
+    fn is_zero(&self) -> bool {
+        <Fr as Zero>::is_zero(self)
+    }
+
+    fn inverse(self) -> Option<Self> {
+        ark_ff::Field::inverse(&self)
+    }
+
+    fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        Fr::from_bigint(bigint(bytes))
+    }
+
+    fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Fr::from_be_bytes_mod_order(bytes)
+    }
+
+    fn to_be_bytes(&self) -> [u8; 32] {
+        be_bytes(self.into_bigint())
+    }
+}
+
+impl Point for G1Projective {
+    type Scalar = Fr;
+
+    const COORDINATES: usize = 2;
+    const COORDINATE_LEN: usize = 32;
+
+    fn zero() -> Self {
+        <G1Projective as Zero>::zero()
+    }
+
+    fn generator() -> Self {
+        <G1Projective as Group>::generator()
+    }
+
+    fn is_zero(&self) -> bool {
+        <G1Projective as Zero>::is_zero(self)
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        let affine = self.into_affine();
+        let (x, y) = affine.xy().unwrap_or((&Fq::ZERO, &Fq::ZERO));
+        for (chunk, coordinate) in out.chunks_exact_mut(32).zip([x, y]) {
+            chunk.copy_from_slice(&be_bytes(coordinate.into_bigint()));
+        }
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, PointError> {
+        let [x, y] = read_coordinates::<2>(data)?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(<G1Projective as Zero>::zero());
+        }
+        checked(G1Affine::new_unchecked(x, y))
+    }
+
+    fn decode_jacobian(xy: &[u8], z: &[u8]) -> Result<Self, PointError> {
+        let [x, y, z] = read_coordinates::<3>(&[xy, z].concat())?;
+        checked(G1Projective::new_unchecked(x, y, z).into_affine())
+    }
+}
+
+impl Point for G2Projective {
+    type Scalar = Fr;
+
+    const COORDINATES: usize = 4;
+    const COORDINATE_LEN: usize = 32;
+
+    fn zero() -> Self {
+        <G2Projective as Zero>::zero()
+    }
+
+    fn generator() -> Self {
+        <G2Projective as Group>::generator()
+    }
+
+    fn is_zero(&self) -> bool {
+        <G2Projective as Zero>::is_zero(self)
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        let affine = self.into_affine();
+        let (x, y) = affine.xy().unwrap_or((&Fq2::ZERO, &Fq2::ZERO));
+        for (chunk, coordinate) in out.chunks_exact_mut(32).zip([x.c1, x.c0, y.c1, y.c0]) {
+            chunk.copy_from_slice(&be_bytes(coordinate.into_bigint()));
+        }
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, PointError> {
+        let [x_im, x_re, y_im, y_re] = read_coordinates::<4>(data)?;
+        let x = Fq2::new(x_re, x_im);
+        let y = Fq2::new(y_re, y_im);
+        if x.is_zero() && y.is_zero() {
+            return Ok(<G2Projective as Zero>::zero());
+        }
+        checked(G2Affine::new_unchecked(x, y))
+    }
+
+    fn decode_jacobian(xy: &[u8], z: &[u8]) -> Result<Self, PointError> {
+        let [x_im, x_re, y_im, y_re, z_im, z_re] = read_coordinates::<6>(&[xy, z].concat())?;
+        let point = G2Projective::new_unchecked(
+            Fq2::new(x_re, x_im),
+            Fq2::new(y_re, y_im),
+            Fq2::new(z_re, z_im),
+        );
+        checked(point.into_affine())
+    }
+}
+
+fn checked<P: SWCurveConfig>(point: Affine<P>) -> Result<Projective<P>, PointError> {
+    if !point.is_on_curve() {
+        return Err(PointError::InvalidPoint {
+            error: GroupError::NotOnCurve,
+        });
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(PointError::InvalidPoint {
+            error: GroupError::NotInSubgroup,
+        });
+    }
+    Ok(point.into())
+}
+
+fn bigint(bytes: &[u8; 32]) -> BigInt<4> {
+    let mut limbs = [0; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("Cannot fail: is a 8-byte chunk"));
+    }
+    BigInt(limbs)
+}
+
+fn be_bytes(value: BigInt<4>) -> [u8; 32] {
+    let mut out = [0; 32];
+    for (chunk, limb) in out.rchunks_exact_mut(8).zip(value.0) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    out
+}
+
+fn read_coordinates<const N: usize>(data: &[u8]) -> Result<[Fq; N], PointError> {
+    if data.len() != N * 32 {
+        return Err(PointError::InvalidLength {
+            len: data.len(),
+            expected: N * 32,
+        });
+    }
+    let mut coordinates = [Fq::ZERO; N];
+    for (index, (coordinate, chunk)) in coordinates
+        .iter_mut()
+        .zip(data.chunks_exact(32))
+        .enumerate()
+    {
+        let bytes = chunk.try_into().expect("Cannot fail: is a 32-byte chunk");
+        *coordinate =
+            Fq::from_bigint(bigint(bytes)).ok_or(PointError::InvalidCoordinate { index })?;
+    }
+    Ok(coordinates)
+}
+
+impl Convert<Fr> for substrate_bn::Fr {
+    fn convert(self) -> Fr {
+        Fr::from_bigint(bigint(&Field::to_be_bytes(&self))).expect("BUG: same modulus")
+    }
+}
+
+impl Convert<substrate_bn::Fr> for Fr {
+    fn convert(self) -> substrate_bn::Fr {
+        Field::from_be_bytes(&be_bytes(self.into_bigint())).expect("BUG: same modulus")
+    }
+}
+
+impl Convert<Fq> for substrate_bn::Fq {
+    fn convert(self) -> Fq {
+        let mut bytes = [0; 32];
+        self.into_u256()
+            .to_big_endian(&mut bytes)
+            .expect("Cannot fail: is a 32-byte array");
+        Fq::from_bigint(bigint(&bytes)).expect("BUG: same modulus")
+    }
+}
+
+impl Convert<substrate_bn::Fq> for Fq {
+    fn convert(self) -> substrate_bn::Fq {
+        let value = U256::from_slice(&be_bytes(self.into_bigint()))
+            .expect("Cannot fail: is a 32-byte array");
+        substrate_bn::Fq::from_u256(value).expect("BUG: same modulus")
+    }
+}
+
+impl Convert<Fq2> for substrate_bn::Fq2 {
+    fn convert(self) -> Fq2 {
+        Fq2::new(self.real().convert(), self.imaginary().convert())
+    }
+}
+
+impl Convert<substrate_bn::Fq2> for Fq2 {
+    fn convert(self) -> substrate_bn::Fq2 {
+        substrate_bn::Fq2::new(self.c0.convert(), self.c1.convert())
+    }
+}
+
+impl Convert<G1Projective> for substrate_bn::G1 {
+    fn convert(self) -> G1Projective {
+        AffineG1::from_jacobian(self)
+            .map(|p| G1Affine::new_unchecked(p.x().convert(), p.y().convert()).into())
+            .unwrap_or_else(<G1Projective as Zero>::zero)
+    }
+}
+
+impl Convert<substrate_bn::G1> for G1Projective {
+    fn convert(self) -> substrate_bn::G1 {
+        self.into_affine()
+            .xy()
+            .map(|(x, y)| substrate_bn::G1::new(x.convert(), y.convert(), substrate_bn::Fq::one()))
+            .unwrap_or_else(<substrate_bn::G1 as Point>::zero)
+    }
+}
+
+impl Convert<G2Projective> for substrate_bn::G2 {
+    fn convert(self) -> G2Projective {
+        AffineG2::from_jacobian(self)
+            .map(|p| G2Affine::new_unchecked(p.x().convert(), p.y().convert()).into())
+            .unwrap_or_else(<G2Projective as Zero>::zero)
+    }
+}
+
+impl Convert<substrate_bn::G2> for G2Projective {
+    fn convert(self) -> substrate_bn::G2 {
+        self.into_affine()
+            .xy()
+            .map(|(x, y)| substrate_bn::G2::new(x.convert(), y.convert(), substrate_bn::Fq2::one()))
+            .unwrap_or_else(<substrate_bn::G2 as Point>::zero)
+    }
+}
+
+impl From<Proof<Bn254>> for Proof<ArkBn254> {
+    fn from(proof: Proof<Bn254>) -> Self {
+        convert_proof(proof)
+    }
+}
+
+impl From<Proof<ArkBn254>> for Proof<Bn254> {
+    fn from(proof: Proof<ArkBn254>) -> Self {
+        convert_proof(proof)
+    }
+}
+
+impl From<VerificationKey<Bn254>> for VerificationKey<ArkBn254> {
+    fn from(vk: VerificationKey<Bn254>) -> Self {
+        convert_key(vk)
+    }
+}
+
+impl From<VerificationKey<ArkBn254>> for VerificationKey<Bn254> {
+    fn from(vk: VerificationKey<ArkBn254>) -> Self {
+        convert_key(vk)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    #[test]
+    fn read_the_scalars_in_canonical_form() {
+        let mut modulus = [0; 32];
+        modulus.copy_from_slice(&ark_ff::BigInteger::to_bytes_be(&Fr::MODULUS));
+
+        assert_eq!(None, Fr::from_be_bytes(&modulus));
+        assert_eq!(<Fr as Zero>::zero(), Fr::from_be_bytes_reduced(&modulus));
+        assert_eq!(
+            substrate_bn::Fr::from_u64(7).to_be_bytes(),
+            <Fr as Field>::from_u64(7).to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn encode_the_points_as_substrate_bn() {
+        let five = <Fr as Field>::from_u64(5);
+        let mut expected = [0; 128];
+        let mut data = [0; 128];
+
+        (<substrate_bn::G1 as Point>::generator() * five.convert()).encode(&mut expected[..64]);
+        (<G1Projective as Point>::generator() * five).encode(&mut data[..64]);
+        assert_eq!(expected, data);
+
+        (<substrate_bn::G2 as Point>::generator() * five.convert()).encode(&mut expected);
+        (<G2Projective as Point>::generator() * five).encode(&mut data);
+        assert_eq!(expected, data);
+    }
+
+    #[test]
+    fn encode_and_decode_the_points() {
+        let g1 = <G1Projective as Point>::generator() * <Fr as Field>::from_u64(5);
+        let g2 = <G2Projective as Point>::generator() * <Fr as Field>::from_u64(5);
+        let mut g1_data = [0; 64];
+        let mut g2_data = [0; 128];
+
+        g1.encode(&mut g1_data);
+        g2.encode(&mut g2_data);
+
+        assert_eq!(g1, G1Projective::decode(&g1_data).unwrap());
+        assert_eq!(g2, G2Projective::decode(&g2_data).unwrap());
+        assert!(Point::is_zero(&G2Projective::decode(&[0; 128]).unwrap()));
+    }
+
+    #[test]
+    fn report_the_invalid_coordinate() {
+        let mut data = [0; 64];
+        <G1Projective as Point>::generator().encode(&mut data);
+        data[32..].fill(0xff);
+
+        assert!(matches!(
+            G1Projective::decode(&data),
+            Err(PointError::InvalidCoordinate { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn reject_a_point_not_on_curve() {
+        let mut data = [0; 64];
+        data[31] = 1;
+
+        assert!(matches!(
+            G1Projective::decode(&data),
+            Err(PointError::InvalidPoint {
+                error: GroupError::NotOnCurve
+            })
+        ));
+    }
+
+    #[test]
+    fn convert_the_points_at_infinity() {
+        let g1: substrate_bn::G1 = <G1Projective as Point>::zero().convert();
+        let g2: G2Projective = <substrate_bn::G2 as Point>::zero().convert();
+
+        assert!(Point::is_zero(&g1));
+        assert!(Point::is_zero(&g2));
+    }
+
+    #[test]
+    fn check_the_pairings_on_the_prepared_points() {
+        let g1 = <G1Projective as Point>::generator();
+        let g2 = <G2Projective as Point>::generator();
+        let five = <Fr as Field>::from_u64(5);
+        let g2_prepared = ArkBn254::prepare(&g2);
+        let g2_five_prepared = ArkBn254::prepare(&(g2 * five));
+
+        assert!(ArkBn254::prepared_pairing_check(&[
+            (g1 * five, &g2_prepared),
+            (-g1, &g2_five_prepared)
+        ]));
+        assert!(!ArkBn254::prepared_pairing_check(&[
+            (g1, &g2_prepared),
+            (-g1, &g2_five_prepared)
+        ]));
+    }
+
+    #[test]
+    fn reject_the_wrong_length() {
+        assert!(matches!(
+            G1Projective::decode(&[]),
+            Err(PointError::InvalidLength {
+                len: 0,
+                expected: 64
+            })
+        ));
+        assert!(matches!(
+            G2Projective::decode(&[0; 127]),
+            Err(PointError::InvalidLength {
+                len: 127,
+                expected: 128
+            })
+        ));
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The snarkjs' `bn128` curve on the `substrate-bn` arithmetic.
+
+use alloc::vec::Vec;
+use substrate_bn::{
+    arith::U256, pairing_batch, AffineG1, AffineG2, Fq, Fq2, Fr, Group, Gt, G1, G2,
+};
+
+use super::{Curve, Field, GroupError, Point, PointError};
+use crate::{macros::u256, utils::IntoFq as _};
+
+/// The scalar field modulus `r`: `substrate-bn` exposes just the base field one.
+#[cfg(any(feature = "zkey", feature = "constant-time"))]
+pub(crate) const FR_MODULUS: U256 =
+    u256!("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+
+/// The BN254 (`bn128`) curve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bn254;
+
+impl Curve for Bn254 {
+    type Fr = Fr;
+    type G1 = G1;
+    type G2 = G2;
+    type G2Prepared = G2;
+
+    const NAME: &'static str = "bn128";
+    const MAX_POWER: u8 = 28;
+
+    fn pairing_check(pairs: &[(G1, G2)]) -> bool {
+        pairing_batch(pairs) == Gt::one()
+    }
+
+    /// `substrate-bn` doesn't expose the line coefficients: it computes them at every pairing.
+    fn prepare(g2: &G2) -> G2 {
+        *g2
+    }
+
+    fn prepared_pairing_check(pairs: &[(G1, &G2)]) -> bool {
+        let pairs = pairs.iter().map(|(g1, g2)| (*g1, **g2)).collect::<Vec<_>>();
+        Self::pairing_check(&pairs)
+    }
+}
+
+impl Field for Fr {
+    fn zero() -> Self {
+        Fr::zero()
+    }
+
+    fn one() -> Self {
+        Fr::one()
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Fr::new(U256::from(value)).expect("BUG: u64 is always a member of Fr")
+    }
+
+    fn is_zero(&self) -> bool {
+        Fr::is_zero(self)
+    }
+
+    fn inverse(self) -> Option<Self> {
+        Fr::inverse(&self)
+    }
+
+    fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        Fr::new(U256::from_slice(bytes).expect("Cannot fail: is a 32-byte array"))
+    }
+
+    fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Fr::from_slice(bytes).expect("Cannot fail: is a 32-byte array")
+    }
+
+    fn to_be_bytes(&self) -> [u8; 32] {
+        fq_bytes(self.into_u256())
+    }
+}
+
+impl Point for G1 {
+    type Scalar = Fr;
+
+    const COORDINATES: usize = 2;
+    const COORDINATE_LEN: usize = 32;
+
+    fn zero() -> Self {
+        <G1 as Group>::zero()
+    }
+
+    fn generator() -> Self {
+        AffineG1::new(Fq::one(), 2.into_fq())
+            .expect("(1, 2) Should be a valid G1 point")
+            .into()
+    }
+
+    fn is_zero(&self) -> bool {
+        <G1 as Group>::is_zero(self)
+    }
+
+    fn normalize(&mut self) {
+        <G1 as Group>::normalize(self)
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        let (x, y) = AffineG1::from_jacobian(*self)
+            .map(|p| (p.x(), p.y()))
+            .unwrap_or((Fq::zero(), Fq::zero()));
+        for (chunk, coordinate) in out.chunks_exact_mut(32).zip([x, y]) {
+            chunk.copy_from_slice(&fq_bytes(coordinate.into_u256()));
+        }
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, PointError> {
+        let [x, y] = read_coordinates::<2>(data)?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(<G1 as Group>::zero());
+        }
+        AffineG1::new(x, y)
+            .map(Into::into)
+            .map_err(|error| PointError::InvalidPoint {
+                error: error.into(),
+            })
+    }
+
+    fn decode_jacobian(xy: &[u8], z: &[u8]) -> Result<Self, PointError> {
+        let [x, y, z] = read_coordinates::<3>(&[xy, z].concat())?;
+        AffineG1::from_jacobian(G1::new(x, y, z)).map_or(Ok(<G1 as Group>::zero()), |p| {
+            AffineG1::new(p.x(), p.y())
+                .map(Into::into)
+                .map_err(|error| PointError::InvalidPoint {
+                    error: error.into(),
+                })
+        })
+    }
+}
+
+/// The G2 generator in EIP-197 ordering.
+const G2_X1: U256 = u256!("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed");
+const G2_X2: U256 = u256!("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2");
+const G2_Y1: U256 = u256!("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa");
+const G2_Y2: U256 = u256!("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b");
+
+impl Point for G2 {
+    type Scalar = Fr;
+
+    const COORDINATES: usize = 4;
+    const COORDINATE_LEN: usize = 32;
+
+    fn zero() -> Self {
+        <G2 as Group>::zero()
+    }
+
+    fn generator() -> Self {
+        AffineG2::new(
+            Fq2::new(fq(G2_X1), fq(G2_X2)),
+            Fq2::new(fq(G2_Y1), fq(G2_Y2)),
+        )
+        .expect("Should be on curve")
+        .into()
+    }
+
+    fn is_zero(&self) -> bool {
+        <G2 as Group>::is_zero(self)
+    }
+
+    fn normalize(&mut self) {
+        <G2 as Group>::normalize(self)
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        let (x, y) = AffineG2::from_jacobian(*self)
+            .map(|p| (p.x(), p.y()))
+            .unwrap_or((Fq2::zero(), Fq2::zero()));
+        for (chunk, coordinate) in
+            out.chunks_exact_mut(32)
+                .zip([x.imaginary(), x.real(), y.imaginary(), y.real()])
+        {
+            chunk.copy_from_slice(&fq_bytes(coordinate.into_u256()));
+        }
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, PointError> {
+        let [x_im, x_re, y_im, y_re] = read_coordinates::<4>(data)?;
+        let x = Fq2::new(x_re, x_im);
+        let y = Fq2::new(y_re, y_im);
+        if x.is_zero() && y.is_zero() {
+            return Ok(<G2 as Group>::zero());
+        }
+        // `AffineG2::new` checks that the point is on the curve and in the right subgroup
+        AffineG2::new(x, y)
+            .map(Into::into)
+            .map_err(|error| PointError::InvalidPoint {
+                error: error.into(),
+            })
+    }
+
+    fn decode_jacobian(xy: &[u8], z: &[u8]) -> Result<Self, PointError> {
+        let [x_im, x_re, y_im, y_re, z_im, z_re] = read_coordinates::<6>(&[xy, z].concat())?;
+        let point = G2::new(
+            Fq2::new(x_re, x_im),
+            Fq2::new(y_re, y_im),
+            Fq2::new(z_re, z_im),
+        );
+        AffineG2::from_jacobian(point).map_or(Ok(<G2 as Group>::zero()), |p| {
+            AffineG2::new(p.x(), p.y())
+                .map(Into::into)
+                .map_err(|error| PointError::InvalidPoint {
+                    error: error.into(),
+                })
+        })
+    }
+}
+
+impl From<substrate_bn::GroupError> for GroupError {
+    fn from(error: substrate_bn::GroupError) -> Self {
+        match error {
+            substrate_bn::GroupError::NotOnCurve => GroupError::NotOnCurve,
+            substrate_bn::GroupError::NotInSubgroup => GroupError::NotInSubgroup,
+        }
+    }
+}
+
+fn fq(value: U256) -> Fq {
+    Fq::from_u256(value).expect("BUG: should be hardcoded")
+}
+
+fn fq_bytes(value: U256) -> [u8; 32] {
+    let mut out = [0; 32];
+    value
+        .to_big_endian(&mut out)
+        .expect("Cannot fail: is a 32-byte array");
+    out
+}
+
+fn read_coordinates<const N: usize>(data: &[u8]) -> Result<[Fq; N], PointError> {
+    if data.len() != N * 32 {
+        return Err(PointError::InvalidLength {
+            len: data.len(),
+            expected: N * 32,
+        });
+    }
+    let mut coordinates = [Fq::zero(); N];
+    for (index, (coordinate, chunk)) in coordinates
+        .iter_mut()
+        .zip(data.chunks_exact(32))
+        .enumerate()
+    {
+        let value = U256::from_slice(chunk).expect("Cannot fail: is a 32-byte chunk");
+        *coordinate = Fq::from_u256(value).map_err(|_| PointError::InvalidCoordinate { index })?;
+    }
+    Ok(coordinates)
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    #[test]
+    fn use_the_standard_generators() {
+        assert_eq!(<G1 as Group>::one(), <G1 as Point>::generator());
+        assert_eq!(<G2 as Group>::one(), <G2 as Point>::generator());
+    }
+
+    #[test]
+    fn read_the_scalars_in_canonical_form() {
+        let modulus = fq_bytes(u256!(
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"
+        ));
+
+        assert_eq!(None, Fr::from_be_bytes(&modulus));
+        assert_eq!(Fr::zero(), Fr::from_be_bytes_reduced(&modulus));
+        assert_eq!(
+            fq_bytes(U256::from(7)),
+            <Fr as Field>::from_u64(7).to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn encode_and_decode_the_points() {
+        let g1 = <G1 as Point>::generator() * <Fr as Field>::from_u64(5);
+        let g2 = <G2 as Point>::generator() * <Fr as Field>::from_u64(5);
+        let mut g1_data = [0; 64];
+        let mut g2_data = [0; 128];
+
+        g1.encode(&mut g1_data);
+        g2.encode(&mut g2_data);
+
+        assert_eq!(g1, G1::decode(&g1_data).unwrap());
+        assert_eq!(g2, G2::decode(&g2_data).unwrap());
+    }
+
+    #[test]
+    fn report_the_invalid_coordinate() {
+        let mut data = [0; 64];
+        <G1 as Point>::generator().encode(&mut data);
+        data[32..].fill(0xff);
+
+        assert!(matches!(
+            G1::decode(&data),
+            Err(PointError::InvalidCoordinate { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn reject_a_point_not_on_curve() {
+        let mut data = [0; 64];
+        data[31] = 1;
+
+        assert!(matches!(
+            G1::decode(&data),
+            Err(PointError::InvalidPoint {
+                error: GroupError::NotOnCurve
+            })
+        ));
+    }
+
+    #[test]
+    fn reject_the_wrong_length() {
+        assert!(matches!(
+            G1::decode(&[]),
+            Err(PointError::InvalidLength {
+                len: 0,
+                expected: 64
+            })
+        ));
+        assert!(matches!(
+            G2::decode(&[0; 127]),
+            Err(PointError::InvalidLength {
+                len: 127,
+                expected: 128
+            })
+        ));
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The snarkjs' `bn128` curve with a constant-time scalar field: the [`CtFr`] arithmetic
+//! never branches on, nor indexes the memory by, the values, so the challenges, the
+//! inverses and the Lagrange evaluations don't leak the public inputs through the timing.
+//! The points wrap the `substrate-bn` ones: the scalar multiplications and the pairing
+//! check are still variable time.
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+use substrate_bn::{Fr, G1, G2};
+
+use super::{
+    bn254::FR_MODULUS,
+    convert::{convert_key, convert_proof, Convert},
+    Bn254, Curve, Field, Point, PointError,
+};
+use crate::{Proof, VerificationKey};
+
+/// The BN254 (`bn128`) curve with the constant-time [`CtFr`] scalars: the points are the
+/// `substrate-bn` ones wrapped in [`CtG1`] and [`CtG2`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CtBn254;
+
+impl Curve for CtBn254 {
+    type Fr = CtFr;
+    type G1 = CtG1;
+    type G2 = CtG2;
+    type G2Prepared = <Bn254 as Curve>::G2Prepared;
+
+    const NAME: &'static str = Bn254::NAME;
+    const MAX_POWER: u8 = Bn254::MAX_POWER;
+
+    fn pairing_check(pairs: &[(CtG1, CtG2)]) -> bool {
+        let pairs = pairs
+            .iter()
+            .map(|(g1, g2)| (g1.0, g2.0))
+            .collect::<Vec<_>>();
+        Bn254::pairing_check(&pairs)
+    }
+
+    fn prepare(g2: &CtG2) -> <Bn254 as Curve>::G2Prepared {
+        Bn254::prepare(&g2.0)
+    }
+
+    fn prepared_pairing_check(pairs: &[(CtG1, &<Bn254 as Curve>::G2Prepared)]) -> bool {
+        let pairs = pairs.iter().map(|(g1, g2)| (g1.0, *g2)).collect::<Vec<_>>();
+        Bn254::prepared_pairing_check(&pairs)
+    }
+}
+
+/// The BN254 scalar field modulus `r` as little-endian limbs.
+const MODULUS: [u64; 4] = {
+    let [low, high] = FR_MODULUS.0;
+    [
+        low as u64,
+        (low >> 64) as u64,
+        high as u64,
+        (high >> 64) as u64,
+    ]
+};
+/// The Fermat's inversion exponent `r - 2`.
+const MODULUS_MINUS_TWO: [u64; 4] = [
+    0x43e1f593efffffff,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+/// `-r^-1 mod 2^64`
+const INV: u64 = 0xc2e1f593efffffff;
+/// `2^256 mod r`: the one in Montgomery form.
+const R: [u64; 4] = [
+    0xac96341c4ffffffb,
+    0x36fc76959f60cd29,
+    0x666ea36f7879462e,
+    0x0e0a77c19a07df2f,
+];
+/// `2^512 mod r`: multiply by it to move in Montgomery form.
+const R2: [u64; 4] = [
+    0x1bb8e645ae216da7,
+    0x53fe3ab1e35c59e3,
+    0x8c49833d53bb8085,
+    0x0216d0b17f4e44a5,
+];
+
+/// A BN254 scalar in Montgomery form with a constant-time arithmetic: the only operations
+/// whose time depends on the value are the [`Field::from_be_bytes`] (if it isn't
+/// canonical) and the [`Field::inverse`] (if it's zero) failures.
+#[derive(Clone, Copy, Default)]
+pub struct CtFr([u64; 4]);
+
+impl CtFr {
+    /// `self^exp` with `exp` as little-endian limbs: square and always multiply.
+    fn pow_limbs(self, exp: &[u64]) -> Self {
+        exp.iter().rev().fold(<Self as Field>::one(), |acc, limb| {
+            (0..u64::BITS).rev().fold(acc, |acc, bit| {
+                let acc = acc * acc;
+                Self(select(&acc.0, &(acc * self).0, mask(limb >> bit & 1)))
+            })
+        })
+    }
+}
+
+impl Field for CtFr {
+    fn zero() -> Self {
+        Self([0; 4])
+    }
+
+    fn one() -> Self {
+        Self(R)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self(mul(&[value, 0, 0, 0], &R2))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().fold(0, |acc, limb| acc | limb) == 0
+    }
+
+    fn inverse(self) -> Option<Self> {
+        let inverse = self.pow_limbs(&MODULUS_MINUS_TWO);
+        (!self.is_zero()).then(|| inverse)
+    }
+
+    fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let value = limbs(bytes);
+        let (_, borrow) = sub_limbs(&value, &MODULUS);
+        (borrow != 0).then(|| Self(mul(&value, &R2)))
+    }
+
+    fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        // 2^256 < 6·r: five conditional subtractions reduce any value
+        let value = (0..5).fold(limbs(bytes), |value, _| reduce_once(value));
+        Self(mul(&value, &R2))
+    }
+
+    fn to_be_bytes(&self) -> [u8; 32] {
+        let mut wide = [0; 8];
+        wide[..4].copy_from_slice(&self.0);
+        let mut out = [0; 32];
+        for (chunk, limb) in out.rchunks_exact_mut(8).zip(montgomery_reduce(wide)) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        out
+    }
+
+    fn pow(self, exp: u64) -> Self {
+        self.pow_limbs(&[exp])
+    }
+}
+
+impl PartialEq for CtFr {
+    fn eq(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .zip(other.0)
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+impl Eq for CtFr {}
+
+impl fmt::Debug for CtFr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CtFr(0x")?;
+        for byte in self.to_be_bytes() {
+            write!(f, "{byte:02x}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Add for CtFr {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // Both are less than r < 2^254: the sum doesn't overflow
+        Self(reduce_once(add_limbs(&self.0, &rhs.0).0))
+    }
+}
+
+impl Sub for CtFr {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let (diff, borrow) = sub_limbs(&self.0, &rhs.0);
+        Self(add_limbs(&diff, &MODULUS.map(|limb| limb & borrow)).0)
+    }
+}
+
+impl Neg for CtFr {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        <Self as Field>::zero() - self
+    }
+}
+
+impl Mul for CtFr {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul(&self.0, &rhs.0))
+    }
+}
+
+/// The all ones mask if `bit` is 1, all zeros if it's 0.
+fn mask(bit: u64) -> u64 {
+    bit.wrapping_neg()
+}
+
+/// `a` if `mask` is all zeros, `b` if it's all ones.
+fn select(a: &[u64; 4], b: &[u64; 4], mask: u64) -> [u64; 4] {
+    let mut out = [0; 4];
+    for (i, limb) in out.iter_mut().enumerate() {
+        *limb = a[i] ^ (mask & (a[i] ^ b[i]));
+    }
+    out
+}
+
+fn limbs(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut limbs = [0; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("Cannot fail: is a 8-byte chunk"));
+    }
+    limbs
+}
+
+/// `a + b + carry` and the new carry.
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let ret = a as u128 + b as u128 + carry as u128;
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// `a - b - borrow` and the new borrow as mask: `borrow` is a mask too.
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let ret = (a as u128).wrapping_sub(b as u128 + (borrow >> 63) as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// `a + b·c + carry` and the new carry.
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let ret = a as u128 + b as u128 * c as u128 + carry as u128;
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// `a + b` and the carry.
+fn add_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut out = [0; 4];
+    let mut carry = 0;
+    for (i, limb) in out.iter_mut().enumerate() {
+        (*limb, carry) = adc(a[i], b[i], carry);
+    }
+    (out, carry)
+}
+
+/// `a - b` and the borrow mask: all ones if `a < b`.
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut out = [0; 4];
+    let mut borrow = 0;
+    for (i, limb) in out.iter_mut().enumerate() {
+        (*limb, borrow) = sbb(a[i], b[i], borrow);
+    }
+    (out, borrow)
+}
+
+/// Subtract the modulus if `value` isn't less than it.
+fn reduce_once(value: [u64; 4]) -> [u64; 4] {
+    let (diff, borrow) = sub_limbs(&value, &MODULUS);
+    select(&diff, &value, borrow)
+}
+
+/// The Montgomery product `a·b·R^-1`.
+fn mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut wide = [0; 8];
+    for i in 0..4 {
+        let mut carry = 0;
+        for j in 0..4 {
+            (wide[i + j], carry) = mac(wide[i + j], a[i], b[j], carry);
+        }
+        wide[i + 4] = carry;
+    }
+    montgomery_reduce(wide)
+}
+
+/// The Montgomery reduction `wide·R^-1`.
+fn montgomery_reduce(mut wide: [u64; 8]) -> [u64; 4] {
+    let mut high_carry = 0;
+    for i in 0..4 {
+        let k = wide[i].wrapping_mul(INV);
+        let mut carry = 0;
+        for j in 0..4 {
+            (wide[i + j], carry) = mac(wide[i + j], k, MODULUS[j], carry);
+        }
+        (wide[i + 4], high_carry) = adc(wide[i + 4], carry, high_carry);
+    }
+    reduce_once([wide[4], wide[5], wide[6], wide[7]])
+}
+
+impl Convert<CtFr> for Fr {
+    fn convert(self) -> CtFr {
+        Field::from_be_bytes(&Field::to_be_bytes(&self)).expect("BUG: same modulus")
+    }
+}
+
+impl Convert<Fr> for CtFr {
+    fn convert(self) -> Fr {
+        Field::from_be_bytes(&self.to_be_bytes()).expect("BUG: same modulus")
+    }
+}
+
+macro_rules! ct_point {
+    ($name:ident, $inner:ident) => {
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<CtFr> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: CtFr) -> Self {
+                Self(self.0 * rhs.convert())
+            }
+        }
+
+        impl Point for $name {
+            type Scalar = CtFr;
+
+            const COORDINATES: usize = <$inner as Point>::COORDINATES;
+            const COORDINATE_LEN: usize = <$inner as Point>::COORDINATE_LEN;
+
+            fn zero() -> Self {
+                Self(<$inner as Point>::zero())
+            }
+
+            fn generator() -> Self {
+                Self(<$inner as Point>::generator())
+            }
+
+            fn is_zero(&self) -> bool {
+                Point::is_zero(&self.0)
+            }
+
+            fn normalize(&mut self) {
+                Point::normalize(&mut self.0)
+            }
+
+            fn encode(&self, out: &mut [u8]) {
+                self.0.encode(out)
+            }
+
+            fn decode(data: &[u8]) -> Result<Self, PointError> {
+                $inner::decode(data).map(Self)
+            }
+
+            fn decode_jacobian(xy: &[u8], z: &[u8]) -> Result<Self, PointError> {
+                $inner::decode_jacobian(xy, z).map(Self)
+            }
+        }
+
+        impl Convert<$name> for $inner {
+            fn convert(self) -> $name {
+                $name(self)
+            }
+        }
+
+        impl Convert<$inner> for $name {
+            fn convert(self) -> $inner {
+                self.0
+            }
+        }
+    };
+}
+
+/// A `substrate-bn` G1 point multiplied by the [`CtFr`] scalars.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CtG1(pub G1);
+
+/// A `substrate-bn` G2 point multiplied by the [`CtFr`] scalars.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CtG2(pub G2);
+
+ct_point!(CtG1, G1);
+ct_point!(CtG2, G2);
+
+impl From<Proof<Bn254>> for Proof<CtBn254> {
+    fn from(proof: Proof<Bn254>) -> Self {
+        convert_proof(proof)
+    }
+}
+
+impl From<Proof<CtBn254>> for Proof<Bn254> {
+    fn from(proof: Proof<CtBn254>) -> Self {
+        convert_proof(proof)
+    }
+}
+
+impl From<VerificationKey<Bn254>> for VerificationKey<CtBn254> {
+    fn from(vk: VerificationKey<Bn254>) -> Self {
+        convert_key(vk)
+    }
+}
+
+impl From<VerificationKey<CtBn254>> for VerificationKey<Bn254> {
+    fn from(vk: VerificationKey<CtBn254>) -> Self {
+        convert_key(vk)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use rand::Rng;
+    use rstest::rstest;
+
+    fn random() -> (Fr, CtFr) {
+        let bytes = rand::thread_rng().gen();
+        (
+            Field::from_be_bytes_reduced(&bytes),
+            Field::from_be_bytes_reduced(&bytes),
+        )
+    }
+
+    fn same(expected: Fr, value: CtFr) {
+        assert_eq!(Field::to_be_bytes(&expected), value.to_be_bytes());
+    }
+
+    #[test]
+    fn have_the_right_montgomery_constants() {
+        assert_eq!(u64::MAX, MODULUS[0].wrapping_mul(INV));
+        assert_eq!(R, mul(&[1, 0, 0, 0], &R2));
+        same(<Fr as Field>::one(), CtFr(R));
+    }
+
+    #[rstest]
+    fn compute_as_substrate_bn(#[values(0, 1, 2, 3, 4, 5, 6, 7)] _round: u8) {
+        let (a, ct_a) = random();
+        let (b, ct_b) = random();
+
+        same(a + b, ct_a + ct_b);
+        same(a - b, ct_a - ct_b);
+        same(b - a, ct_b - ct_a);
+        same(-a, -ct_a);
+        same(a * b, ct_a * ct_b);
+        same(a.inverse().unwrap(), ct_a.inverse().unwrap());
+        same(Field::pow(a, 1 << 20), ct_a.pow(1 << 20));
+        same(Field::pow(a, u64::MAX), ct_a.pow(u64::MAX));
+        assert_eq!(ct_a, ct_a + ct_b - ct_b);
+        assert_ne!(ct_a, ct_a + <CtFr as Field>::one());
+    }
+
+    #[test]
+    fn handle_the_edge_values() {
+        let zero = <CtFr as Field>::zero();
+        let one = <CtFr as Field>::one();
+        let minus_one = -one;
+
+        assert_eq!(None, zero.inverse());
+        assert_eq!(zero, -zero);
+        assert_eq!(zero, minus_one + one);
+        assert_eq!(one, minus_one * minus_one);
+        assert_eq!(one, zero.pow(0));
+        same(-<Fr as Field>::one(), minus_one);
+    }
+
+    #[test]
+    fn read_the_scalars_in_canonical_form() {
+        let mut modulus = [0; 32];
+        for (chunk, limb) in modulus.rchunks_exact_mut(8).zip(MODULUS) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+
+        assert_eq!(None, CtFr::from_be_bytes(&modulus));
+        assert_eq!(
+            <CtFr as Field>::zero(),
+            CtFr::from_be_bytes_reduced(&modulus)
+        );
+        same(
+            Fr::from_be_bytes_reduced(&[0xff; 32]),
+            CtFr::from_be_bytes_reduced(&[0xff; 32]),
+        );
+        same(<Fr as Field>::from_u64(7), CtFr::from_u64(7));
+    }
+
+    #[test]
+    fn multiply_the_points_as_substrate_bn() {
+        let (five, ct_five) = (<Fr as Field>::from_u64(5), CtFr::from_u64(5));
+
+        assert_eq!(
+            <G1 as Point>::generator() * five,
+            (<CtG1 as Point>::generator() * ct_five).0
+        );
+        assert_eq!(
+            <G2 as Point>::generator() * five,
+            (<CtG2 as Point>::generator() * ct_five).0
+        );
+    }
+}
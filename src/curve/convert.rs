@@ -0,0 +1,86 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The lossless conversions between the proofs and the keys of the curve backends that
+//! represent the same curve.
+
+use super::Curve;
+use crate::{
+    proof::{Evaluations, Polynomials},
+    Proof, VerificationKey,
+};
+
+/// Lossless conversion between the values of two backends: both represent the same
+/// fields and groups, so no check is needed.
+pub(crate) trait Convert<T> {
+    fn convert(self) -> T;
+}
+
+pub(crate) fn convert_proof<A: Curve, B: Curve>(proof: Proof<A>) -> Proof<B>
+where
+    A::Fr: Convert<B::Fr>,
+    A::G1: Convert<B::G1>,
+{
+    let Polynomials { c1, c2, w1, w2 } = proof.polynomials;
+    let e = proof.evaluations;
+    Proof {
+        polynomials: Polynomials {
+            c1: c1.convert(),
+            c2: c2.convert(),
+            w1: w1.convert(),
+            w2: w2.convert(),
+        },
+        evaluations: Evaluations {
+            ql: e.ql.convert(),
+            qr: e.qr.convert(),
+            qm: e.qm.convert(),
+            qo: e.qo.convert(),
+            qc: e.qc.convert(),
+            s1: e.s1.convert(),
+            s2: e.s2.convert(),
+            s3: e.s3.convert(),
+            a: e.a.convert(),
+            b: e.b.convert(),
+            c: e.c.convert(),
+            z: e.z.convert(),
+            zw: e.zw.convert(),
+            t1w: e.t1w.convert(),
+            t2w: e.t2w.convert(),
+            inv: e.inv.convert(),
+        },
+    }
+}
+
+pub(crate) fn convert_key<A: Curve, B: Curve>(vk: VerificationKey<A>) -> VerificationKey<B>
+where
+    A::Fr: Convert<B::Fr>,
+    A::G1: Convert<B::G1>,
+    A::G2: Convert<B::G2>,
+{
+    VerificationKey {
+        n_public: vk.n_public,
+        power: vk.power,
+        k1: vk.k1.convert(),
+        k2: vk.k2.convert(),
+        w: vk.w.convert(),
+        w3: vk.w3.convert(),
+        w4: vk.w4.convert(),
+        w8: vk.w8.convert(),
+        wr: vk.wr.convert(),
+        x2: vk.x2.convert(),
+        c0: vk.c0.convert(),
+    }
+}
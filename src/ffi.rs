@@ -0,0 +1,211 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The C ABI of the verifier (`ffi` feature): the functions and the result codes declared in
+//! `include/fflonk_verifier.h`, that is generated from this module by `cbindgen`. The codes
+//! are part of the ABI: they are never renumbered and new ones only take new values.
+
+use std::{os::raw::c_char, panic::catch_unwind};
+
+use crate::{proof::ProofDataError, verify, Bn254, Proof, Public, VerificationKey, VerifyError};
+
+/// The proof is valid.
+pub const FFLONK_OK: i32 = 0;
+/// A null pointer is given for a non empty buffer.
+pub const FFLONK_ERROR_NULL_POINTER: i32 = -1;
+/// The verification key bytes are not a valid key.
+pub const FFLONK_ERROR_INVALID_KEY_DATA: i32 = -2;
+/// A proof's evaluation or commitment coordinate is not a field element.
+pub const FFLONK_ERROR_PROOF_INVALID_FIELD: i32 = -3;
+/// A proof's commitment is not a G1 point.
+pub const FFLONK_ERROR_PROOF_INVALID_POINT: i32 = -4;
+/// The proof bytes are not 768.
+pub const FFLONK_ERROR_PROOF_WRONG_LENGTH: i32 = -5;
+/// The public inputs bytes are not a multiple of 32.
+pub const FFLONK_ERROR_PUBLIC_INPUTS_WRONG_LENGTH: i32 = -6;
+/// The proof's inverse is wrong.
+pub const FFLONK_ERROR_INVALID_INVERSE: i32 = -7;
+/// The number of public inputs doesn't match the verification key one.
+pub const FFLONK_ERROR_INVALID_PUBLIC_INPUTS_NUMBER: i32 = -8;
+/// A public input is not a scalar field element.
+pub const FFLONK_ERROR_INVALID_PUBLIC_INPUT: i32 = -9;
+/// A proof's commitment is the point at infinity.
+pub const FFLONK_ERROR_POINT_AT_INFINITY: i32 = -10;
+/// A challenge makes the verifier equations degenerate.
+pub const FFLONK_ERROR_DEGENERATE_CHALLENGE: i32 = -11;
+/// The verification key is malformed.
+pub const FFLONK_ERROR_INVALID_VERIFICATION_KEY: i32 = -12;
+/// The pairing check failed: the proof is not valid.
+pub const FFLONK_ERROR_NOT_PAIRING: i32 = -13;
+/// The verifier panicked: it's a bug, please report it.
+pub const FFLONK_ERROR_PANIC: i32 = -14;
+
+/// Verify the BN254 proof against the public inputs:
+/// - `vk` is the 480 bytes key encoding (`VerificationKey::to_bytes()`),
+/// - `proof` is the 768 bytes proof (24 big-endian words),
+/// - `pubs` is the concatenation of the 32 bytes big-endian public inputs.
+///
+/// Return `FFLONK_OK` if the proof is valid or one of the `FFLONK_ERROR_*` codes.
+///
+/// # Safety
+///
+/// Every pointer should be valid to read the given number of bytes or, if the length is 0,
+/// can be null.
+#[no_mangle]
+pub unsafe extern "C" fn fflonk_verify(
+    vk_ptr: *const u8,
+    vk_len: usize,
+    proof_ptr: *const u8,
+    proof_len: usize,
+    pubs_ptr: *const u8,
+    pubs_len: usize,
+) -> i32 {
+    let (vk, proof, pubs) = match (
+        slice(vk_ptr, vk_len),
+        slice(proof_ptr, proof_len),
+        slice(pubs_ptr, pubs_len),
+    ) {
+        (Some(vk), Some(proof), Some(pubs)) => (vk, proof, pubs),
+        _ => return FFLONK_ERROR_NULL_POINTER,
+    };
+    catch_unwind(|| verify_bytes(vk, proof, pubs))
+        .unwrap_or(Err(FFLONK_ERROR_PANIC))
+        .err()
+        .unwrap_or(FFLONK_OK)
+}
+
+/// Return the description of the given result code as a static NUL terminated string.
+#[no_mangle]
+pub extern "C" fn fflonk_error_message(code: i32) -> *const c_char {
+    let message: &'static [u8] = match code {
+        FFLONK_OK => b"Valid proof\0",
+        FFLONK_ERROR_NULL_POINTER => b"Null pointer for a non empty buffer\0",
+        FFLONK_ERROR_INVALID_KEY_DATA => b"Invalid verification key data\0",
+        FFLONK_ERROR_PROOF_INVALID_FIELD => b"Invalid proof field element\0",
+        FFLONK_ERROR_PROOF_INVALID_POINT => b"Invalid proof commitment\0",
+        FFLONK_ERROR_PROOF_WRONG_LENGTH => b"Invalid proof length\0",
+        FFLONK_ERROR_PUBLIC_INPUTS_WRONG_LENGTH => b"Invalid public inputs length\0",
+        FFLONK_ERROR_INVALID_INVERSE => b"Invalid provided inverse\0",
+        FFLONK_ERROR_INVALID_PUBLIC_INPUTS_NUMBER => b"Invalid public inputs number\0",
+        FFLONK_ERROR_INVALID_PUBLIC_INPUT => b"Invalid public input\0",
+        FFLONK_ERROR_POINT_AT_INFINITY => b"Proof commitment at infinity\0",
+        FFLONK_ERROR_DEGENERATE_CHALLENGE => b"Degenerate challenge\0",
+        FFLONK_ERROR_INVALID_VERIFICATION_KEY => b"Invalid verification key\0",
+        FFLONK_ERROR_NOT_PAIRING => b"Cannot verify paring\0",
+        FFLONK_ERROR_PANIC => b"Verifier panic\0",
+        _ => b"Unknown result code\0",
+    };
+    message.as_ptr().cast()
+}
+
+unsafe fn slice<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (len, ptr.is_null()) {
+        (0, _) => Some(&[]),
+        (_, true) => None,
+        _ => Some(core::slice::from_raw_parts(ptr, len)),
+    }
+}
+
+fn verify_bytes(vk: &[u8], proof: &[u8], pubs: &[u8]) -> Result<(), i32> {
+    let vk = VerificationKey::<Bn254>::from_bytes(vk).map_err(|_| FFLONK_ERROR_INVALID_KEY_DATA)?;
+    let proof = Proof::<Bn254>::from_bytes(proof).map_err(|e| proof_error_code(&e))?;
+    let pubs = Public::try_from(pubs).map_err(|_| FFLONK_ERROR_PUBLIC_INPUTS_WRONG_LENGTH)?;
+    verify(&vk, &proof, &pubs).map_err(|e| verify_error_code(&e))
+}
+
+fn proof_error_code(error: &ProofDataError) -> i32 {
+    match error {
+        ProofDataError::InvalidField { .. } => FFLONK_ERROR_PROOF_INVALID_FIELD,
+        ProofDataError::InvalidGroup { .. } => FFLONK_ERROR_PROOF_INVALID_POINT,
+        ProofDataError::WrongLength { .. } => FFLONK_ERROR_PROOF_WRONG_LENGTH,
+    }
+}
+
+fn verify_error_code(error: &VerifyError) -> i32 {
+    match error {
+        VerifyError::InvalidInverse { .. } => FFLONK_ERROR_INVALID_INVERSE,
+        VerifyError::InvalidPublicInputsNumber { .. } => FFLONK_ERROR_INVALID_PUBLIC_INPUTS_NUMBER,
+        VerifyError::InvalidPublicInput { .. } => FFLONK_ERROR_INVALID_PUBLIC_INPUT,
+        VerifyError::PointAtInfinity { .. } => FFLONK_ERROR_POINT_AT_INFINITY,
+        VerifyError::DegenerateChallenge { .. } => FFLONK_ERROR_DEGENERATE_CHALLENGE,
+        VerifyError::InvalidVerificationKey { .. } => FFLONK_ERROR_INVALID_VERIFICATION_KEY,
+        VerifyError::NotPairing => FFLONK_ERROR_NOT_PAIRING,
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    #[test]
+    fn accept_null_pointers_for_empty_buffers() {
+        let code = unsafe {
+            fflonk_verify(
+                core::ptr::null(),
+                0,
+                core::ptr::null(),
+                0,
+                core::ptr::null(),
+                0,
+            )
+        };
+
+        assert_eq!(FFLONK_ERROR_INVALID_KEY_DATA, code);
+    }
+
+    #[test]
+    fn reject_null_pointers_for_non_empty_buffers() {
+        let vk = VerificationKey::<Bn254>::default().to_bytes();
+
+        let code = unsafe {
+            fflonk_verify(
+                vk.as_ptr(),
+                vk.len(),
+                core::ptr::null(),
+                768,
+                core::ptr::null(),
+                0,
+            )
+        };
+
+        assert_eq!(FFLONK_ERROR_NULL_POINTER, code);
+    }
+
+    #[test]
+    fn describe_every_result_code() {
+        let message = |code| unsafe { CStr::from_ptr(fflonk_error_message(code)) };
+        let unknown = message(1);
+
+        for code in FFLONK_ERROR_PANIC..=FFLONK_OK {
+            assert_ne!(unknown, message(code), "code {code}");
+        }
+    }
+
+    #[cfg(feature = "ffi-header")]
+    #[test]
+    fn keep_the_committed_header_in_sync() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/include/fflonk_verifier.h"));
+        let committed = include_str!("../include/fflonk_verifier.h");
+
+        assert!(
+            generated == committed,
+            "include/fflonk_verifier.h is stale: copy the one generated in {}",
+            env!("OUT_DIR")
+        );
+    }
+}
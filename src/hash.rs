@@ -16,6 +16,8 @@
 
 use substrate_bn::arith::U256;
 
+use crate::utils::IntoBytes;
+
 pub(crate) trait Hasher {
     fn hash(&self) -> [u8; 32];
 }
@@ -27,19 +29,15 @@ impl Hasher for [u8] {
     }
 }
 
-const MAX_HASH_LEN: usize = 25;
-
 impl Hasher for &[U256] {
     fn hash(&self) -> [u8; 32] {
-        if self.len() > MAX_HASH_LEN {
-            panic!("Too many elements in hasher");
-        }
-        let mut buffer = [0_u8; MAX_HASH_LEN * 32];
-        for (pos, d) in self.iter().enumerate() {
-            d.to_big_endian(&mut buffer[pos * 32..(pos + 1) * 32])
-                .expect("BUG: should never fails!");
-        }
-        buffer[0..self.len() * 32].hash()
+        use digest::Digest;
+        self.iter()
+            .fold(sha3::Keccak256::new(), |hasher, d| {
+                hasher.chain_update(d.into_bytes())
+            })
+            .finalize()
+            .into()
     }
 }
 
@@ -81,4 +79,16 @@ mod tests {
         // All challenges are corrected to be Fr element (computed a module)
         assert_eq!(expected, input.hash().into_fr())
     }
+
+    #[test]
+    fn hash_any_number_of_elements_as_their_concatenated_big_endian_bytes() {
+        let mut input = [U256::zero(); 40];
+        let mut bytes = [0_u8; 40 * 32];
+        for (pos, d) in input.iter_mut().enumerate() {
+            *d = U256::from(pos as u64);
+            bytes[pos * 32..(pos + 1) * 32].copy_from_slice(&d.into_bytes());
+        }
+
+        assert_eq!(bytes.as_slice().hash(), input.hash())
+    }
 }
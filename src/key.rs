@@ -14,37 +14,153 @@
 // You should have received a copy of the GNU General Public License
 // along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
 
-use substrate_bn::{Fq, Fq2, Fr, G1, G2};
+use alloc::{string::String, vec::Vec};
+use digest::{consts::U32, Digest};
+use snafu::Snafu;
+use substrate_bn::{arith::U256, FieldError, Fq, Fq2, G1, G2};
 
-use crate::{macros::u256, utils::IntoFr};
+use crate::{
+    curve::{Bn254, Curve, Field, GroupError, Point, PointError},
+    macros::u256,
+    utils::IntoFr,
+};
 
-#[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
-pub struct VerificationKey {
+/// The only protocol supported by this verifier.
+pub const PROTOCOL: &str = "fflonk";
+/// The default curve: use [`Curve::NAME`] for the other ones.
+pub const CURVE: &str = Bn254::NAME;
+
+/// The verification key identifier: the hash of its canonical bytes encoding (see
+/// [`VerificationKey::hash`]).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(transparent)
+)]
+pub struct VkId(pub [u8; 32]);
+
+impl VkId {
+    /// The identifier bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for VkId {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<VkId> for [u8; 32] {
+    fn from(value: VkId) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<[u8]> for VkId {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl core::fmt::LowerHex for VkId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl core::fmt::Display for VkId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:#x}")
+    }
+}
+
+/// Verification Key Error
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum VerificationKeyError {
+    /// The key is not for the fflonk protocol
+    #[snafu(display("Unsupported protocol '{protocol}': expected '{PROTOCOL}'"))]
+    UnsupportedProtocol { protocol: String },
+    /// The key is not for the verifier's curve
+    #[snafu(display("Unsupported curve '{curve}': expected '{expected}'"))]
+    UnsupportedCurve {
+        curve: String,
+        expected: &'static str,
+    },
+    /// The key is not self-consistent
+    #[snafu(display("Invalid verification key: {source}"))]
+    InvalidKey {
+        source: VerificationKeyValidationError,
+    },
+}
+
+/// The [`VerificationKey::validate`] error: the first key's field that is not consistent.
+#[derive(Snafu, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VerificationKeyValidationError {
+    /// The domain is bigger than the curve supports
+    #[snafu(display("Unsupported power {power}: the maximum is {max}"))]
+    UnsupportedPower { power: u8, max: u8 },
+    /// `k1` or `k2` doesn't generate a coset disjoint from the domain and the other one
+    #[snafu(display("Invalid '{field}': it's not a coset separator"))]
+    InvalidCosetSeparator { field: &'static str },
+    /// The root is not a primitive root of unity of the given order
+    #[snafu(display("Invalid '{field}': it's not a primitive root of unity of order {order}"))]
+    NotPrimitiveRoot { field: &'static str, order: u64 },
+    /// `wr` is not a cube root of `w`
+    #[snafu(display("Invalid 'wr': its cube is not 'w'"))]
+    InvalidShift,
+    /// The point is at infinity
+    #[snafu(display("Invalid '{field}': it's the point at infinity"))]
+    PointAtInfinity { field: &'static str },
+}
+
+impl VerificationKeyValidationError {
+    /// The name of the inconsistent field.
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::UnsupportedPower { .. } => "power",
+            Self::InvalidCosetSeparator { field }
+            | Self::NotPrimitiveRoot { field, .. }
+            | Self::PointAtInfinity { field } => field,
+            Self::InvalidShift => "wr",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(
+        try_from = "crate::serde::VerificationKeySerde<C>",
+        into = "crate::serde::VerificationKeySerde<C>",
+        bound = ""
+    )
+)]
+pub struct VerificationKey<C: Curve = Bn254> {
+    /// The number of the circuit's public inputs
+    pub n_public: usize,
     pub power: u8,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub k1: Fr,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub k2: Fr,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub w: Fr,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub w3: Fr,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub w4: Fr,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub w8: Fr,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub wr: Fr,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::g2", rename = "X_2"))]
-    pub x2: G2,
-    #[cfg_attr(feature = "serde", serde(with = "crate::serde::g1", rename = "C0"))]
-    pub c0: G1,
+    pub k1: C::Fr,
+    pub k2: C::Fr,
+    pub w: C::Fr,
+    pub w3: C::Fr,
+    pub w4: C::Fr,
+    pub w8: C::Fr,
+    pub wr: C::Fr,
+    pub x2: C::G2,
+    pub c0: C::G1,
 }
 
 impl Default for VerificationKey {
     fn default() -> Self {
         Self {
+            n_public: 1,
             power: 24,
             k1: substrate_bn::arith::U256::from(2).into_fr(),
             k2: substrate_bn::arith::U256::from(3).into_fr(),
@@ -86,3 +202,498 @@ impl Default for VerificationKey {
         }
     }
 }
+
+/// The size in bytes of [`VerificationKeyRawData`].
+pub const VERIFICATION_KEY_RAW_DATA_LEN: usize = 32 * 15;
+/// The BN254 verification key as fixed size big-endian bytes array: see
+/// [`VerificationKey::to_bytes`] for the layout.
+pub type VerificationKeyRawData = [u8; VERIFICATION_KEY_RAW_DATA_LEN];
+
+/// Verification key bytes decoding error
+#[derive(Snafu, Debug)]
+pub enum VerificationKeyDataError {
+    #[snafu(display("Invalid verification key data length {len}: expected {expected}"))]
+    InvalidLength { len: usize, expected: usize },
+    #[snafu(display("Invalid verification key power {power:?}: should be at most {max}"))]
+    InvalidPower { power: U256, max: u8 },
+    #[snafu(display("Invalid verification key public inputs number {n_public:?}"))]
+    InvalidPublicInputsNumber { n_public: U256 },
+    #[snafu(display("Invalid field verification key data '{field}': {error:?}"))]
+    InvalidField {
+        field: &'static str,
+        error: FieldError,
+    },
+    #[snafu(display("Invalid point verification key data '{field}': {error:?}"))]
+    InvalidGroup {
+        field: &'static str,
+        error: GroupError,
+    },
+}
+
+impl<C: Curve> VerificationKey<C> {
+    /// The size in bytes of the encoded key.
+    pub const ENCODED_LEN: usize =
+        32 * 9 + <C::G1 as Point>::ENCODED_LEN + <C::G2 as Point>::ENCODED_LEN;
+
+    /// Encode the key as big-endian bytes: `power`, `nPublic`, `k1`, `k2`, `w`, `w3`, `w4`,
+    /// `w8` and `wr` as 32-byte words followed by the affine `C0` (`x`, `y`) and the affine
+    /// `X_2` in EIP-197 ordering (`x.im`, `x.re`, `y.im`, `y.re`). The points at infinity
+    /// are encoded as all zeros.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = alloc::vec![0_u8; Self::ENCODED_LEN];
+        let (words, points) = out.split_at_mut(32 * 9);
+        let (c0, x2) = points.split_at_mut(<C::G1 as Point>::ENCODED_LEN);
+        words[31] = self.power;
+        words[32 + 24..64].copy_from_slice(&(self.n_public as u64).to_be_bytes());
+        for (chunk, scalar) in words[64..]
+            .chunks_exact_mut(32)
+            .zip([self.k1, self.k2, self.w, self.w3, self.w4, self.w8, self.wr])
+        {
+            chunk.copy_from_slice(&scalar.to_be_bytes());
+        }
+        self.c0.encode(c0);
+        self.x2.encode(x2);
+        out
+    }
+
+    /// The key identifier: the Keccak256 hash of its [`VerificationKey::to_bytes`] encoding.
+    pub fn hash(&self) -> VkId {
+        self.hash_with::<sha3::Keccak256>()
+    }
+
+    /// The key identifier computed by the `D` hasher over its [`VerificationKey::to_bytes`]
+    /// encoding.
+    pub fn hash_with<D: Digest<OutputSize = U32>>(&self) -> VkId {
+        VkId(D::digest(self.to_bytes()).into())
+    }
+
+    /// Check that the key is self-consistent: `power` is supported by the curve, `w` is a
+    /// primitive `2^power`-th root of unity other than one, `w3`, `w4` and `w8` are
+    /// primitive 3rd, 4th and 8th roots, `wr` is a cube root of `w`, `k1` and `k2` separate
+    /// the domain cosets and `C0` and `X_2` are not at infinity.
+    pub fn validate(&self) -> Result<(), VerificationKeyValidationError> {
+        if self.power > C::MAX_POWER {
+            return Err(VerificationKeyValidationError::UnsupportedPower {
+                power: self.power,
+                max: C::MAX_POWER,
+            });
+        }
+        let one = C::Fr::one();
+        let n = 1_u64 << self.power;
+        let (k1_n, k2_n) = (self.k1.pow(n), self.k2.pow(n));
+        [
+            ("k1", self.k1.is_zero() || k1_n == one),
+            ("k2", self.k2.is_zero() || k2_n == one || k2_n == k1_n),
+        ]
+        .into_iter()
+        .find_map(|(field, invalid)| invalid.then(|| field))
+        .map_or(Ok(()), |field| {
+            Err(VerificationKeyValidationError::InvalidCosetSeparator { field })
+        })?;
+        [
+            ("w", self.w, n),
+            ("w3", self.w3, 3),
+            ("w4", self.w4, 4),
+            ("w8", self.w8, 8),
+        ]
+        .into_iter()
+        .find_map(|(field, root, order)| (!is_primitive_root(root, order)).then(|| (field, order)))
+        .map_or(Ok(()), |(field, order)| {
+            Err(VerificationKeyValidationError::NotPrimitiveRoot { field, order })
+        })?;
+        if self.wr * self.wr * self.wr != self.w {
+            return Err(VerificationKeyValidationError::InvalidShift);
+        }
+        [("c0", self.c0.is_zero()), ("x2", self.x2.is_zero())]
+            .into_iter()
+            .find_map(|(field, invalid)| invalid.then(|| field))
+            .map_or(Ok(()), |field| {
+                Err(VerificationKeyValidationError::PointAtInfinity { field })
+            })
+    }
+
+    /// Decode a key from its [`VerificationKey::to_bytes`] encoding.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, VerificationKeyDataError> {
+        if data.len() != Self::ENCODED_LEN {
+            return Err(VerificationKeyDataError::InvalidLength {
+                len: data.len(),
+                expected: Self::ENCODED_LEN,
+            });
+        }
+        let word = |i: usize| {
+            U256::from_slice(&data[32 * i..32 * (i + 1)]).expect("Cannot fail to read 32 bytes")
+        };
+        let power = word(0);
+        if power > U256::from(C::MAX_POWER as u64) {
+            return Err(VerificationKeyDataError::InvalidPower {
+                power,
+                max: C::MAX_POWER,
+            });
+        }
+        let n_public = word(1);
+        if n_public > U256::from(u32::MAX as u64) {
+            return Err(VerificationKeyDataError::InvalidPublicInputsNumber { n_public });
+        }
+        let fr = |field: &'static str, i: usize| {
+            read_fr::<C::Fr>(
+                field,
+                data[32 * i..32 * (i + 1)]
+                    .try_into()
+                    .expect("Cannot fail: is a 32-byte slice"),
+            )
+        };
+        let (c0, x2) = data[32 * 9..].split_at(<C::G1 as Point>::ENCODED_LEN);
+        Ok(Self {
+            n_public: n_public.0[0] as usize,
+            power: power.0[0] as u8,
+            k1: fr("k1", 2)?,
+            k2: fr("k2", 3)?,
+            w: fr("w", 4)?,
+            w3: fr("w3", 5)?,
+            w4: fr("w4", 6)?,
+            w8: fr("w8", 7)?,
+            wr: fr("wr", 8)?,
+            c0: read_point("C0", c0)?,
+            x2: read_point("X_2", x2)?,
+        })
+    }
+}
+
+impl From<&VerificationKey> for VerificationKeyRawData {
+    fn from(vk: &VerificationKey) -> Self {
+        vk.to_bytes()
+            .try_into()
+            .expect("Cannot fail: a BN254 key is 480 bytes")
+    }
+}
+
+impl From<VerificationKey> for VerificationKeyRawData {
+    fn from(vk: VerificationKey) -> Self {
+        (&vk).into()
+    }
+}
+
+impl<C: Curve> TryFrom<&[u8]> for VerificationKey<C> {
+    type Error = VerificationKeyDataError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(data)
+    }
+}
+
+impl TryFrom<&VerificationKeyRawData> for VerificationKey {
+    type Error = VerificationKeyDataError;
+
+    fn try_from(data: &VerificationKeyRawData) -> Result<Self, Self::Error> {
+        data.as_slice().try_into()
+    }
+}
+
+fn read_fr<F: Field>(field: &'static str, data: &[u8; 32]) -> Result<F, VerificationKeyDataError> {
+    F::from_be_bytes(data).ok_or(VerificationKeyDataError::InvalidField {
+        field,
+        error: FieldError::NotMember,
+    })
+}
+
+fn read_point<P: Point>(field: &'static str, data: &[u8]) -> Result<P, VerificationKeyDataError> {
+    P::decode(data).map_err(|error| match error {
+        PointError::InvalidLength { len, expected } => {
+            VerificationKeyDataError::InvalidLength { len, expected }
+        }
+        PointError::InvalidCoordinate { .. } => VerificationKeyDataError::InvalidField {
+            field,
+            error: FieldError::NotMember,
+        },
+        PointError::InvalidPoint { error } => {
+            VerificationKeyDataError::InvalidGroup { field, error }
+        }
+    })
+}
+
+/// Whether `root` is a primitive root of unity of `order` (a product of 2 and 3 powers);
+/// `1` is rejected because it makes the verifier's denominators vanish.
+fn is_primitive_root<F: Field>(root: F, order: u64) -> bool {
+    let one = F::one();
+    root != one
+        && root.pow(order) == one
+        && [2, 3]
+            .into_iter()
+            .filter(|p| order % p == 0)
+            .all(|p| root.pow(order / p) != one)
+}
+
+#[cfg(test)]
+mod should {
+    use alloc::{format, string::ToString};
+    use rstest::rstest;
+    use substrate_bn::Fr;
+
+    use super::*;
+    use crate::utils::IntoBytes as _;
+
+    const DEFAULT_KEY_RAW_DATA: VerificationKeyRawData = hex_literal::hex!(
+        r#"
+        0000000000000000000000000000000000000000000000000000000000000018
+        0000000000000000000000000000000000000000000000000000000000000001
+        0000000000000000000000000000000000000000000000000000000000000002
+        0000000000000000000000000000000000000000000000000000000000000003
+        0c9fabc7845d50d2852e2a0371c6441f145e0db82e8326961c25f1e3e32b045b
+        30644e72e131a029048b6e193fd84104cc37a73fec2bc5e9b8ca0b2d36636f23
+        30644e72e131a029048b6e193fd841045cea24f6fd736bec231204708f703636
+        2b337de1c8c14f22ec9b9e2f96afef3652627366f8170a0a948dad4ac1bd5e80
+        283ce45a2e5b8e4e78f9fbaf5f6a348bfcfaf76dd28e5ca7121b74ef68fdec2e
+        10711a639fed66ba6cd6001188b8fe7285cb9bd01afc1f90598223550aa57e36
+        28c937a4cb758326763015d30fff3568f5cbed932cdc7c411a435d3de04549ef
+        26186a2d65ee4d2f9c9a5b91f86597d35f192cd120caf7e935d8443d1938e23d
+        30441fd1b5d3370482c42152a8899027716989a6996c2535bc9f7fee8aaef79e
+        1970ea81dd6992adfbc571effb03503adbbb6a857f578403c6c40e22d65b3c02
+        054793348f12c0cf5622c340573cb277586319de359ab9389778f689786b1e48
+        "#
+    );
+
+    #[test]
+    fn encode_the_default_key() {
+        assert_eq!(
+            DEFAULT_KEY_RAW_DATA,
+            VerificationKeyRawData::from(&VerificationKey::default())
+        );
+    }
+
+    #[test]
+    fn decode_the_default_key() {
+        assert_eq!(
+            VerificationKey::default(),
+            VerificationKey::try_from(&DEFAULT_KEY_RAW_DATA).unwrap()
+        );
+    }
+
+    const DEFAULT_KEY_ID: [u8; 32] =
+        hex_literal::hex!("0c94f11324c07919a21cf854213b72023fed8d247b1de903daffaf9d91b1f959");
+
+    #[test]
+    fn hash_the_default_key() {
+        assert_eq!(VkId(DEFAULT_KEY_ID), VerificationKey::default().hash());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn hash_the_json_key() {
+        let vk: VerificationKey =
+            serde_json::from_str(include_str!("../resources/bins/zksync_vk.json")).unwrap();
+
+        assert_eq!(VkId(DEFAULT_KEY_ID), vk.hash());
+    }
+
+    #[test]
+    fn hash_the_key_with_another_hasher() {
+        assert_eq!(
+            VkId(hex_literal::hex!(
+                "4791cbecefe788ee80b90671bd1ac1ce8c87234a0d222e64d5fc200833e6245e"
+            )),
+            VerificationKey::default().hash_with::<sha3::Sha3_256>()
+        );
+    }
+
+    #[test]
+    fn change_the_id_when_the_key_changes() {
+        let vk = VerificationKey {
+            n_public: 2,
+            ..Default::default()
+        };
+
+        assert_ne!(VerificationKey::default().hash(), vk.hash());
+    }
+
+    #[test]
+    fn display_the_id_as_hex() {
+        let id = VkId(DEFAULT_KEY_ID);
+
+        assert_eq!(
+            "0x0c94f11324c07919a21cf854213b72023fed8d247b1de903daffaf9d91b1f959",
+            id.to_string()
+        );
+        assert_eq!(
+            "0c94f11324c07919a21cf854213b72023fed8d247b1de903daffaf9d91b1f959",
+            format!("{id:x}")
+        );
+    }
+
+    #[test]
+    fn validate_the_default_key() {
+        assert_eq!(Ok(()), VerificationKey::default().validate());
+    }
+
+    #[rstest]
+    #[case::power(
+        |vk: &mut VerificationKey| vk.power = 29,
+        VerificationKeyValidationError::UnsupportedPower { power: 29, max: 28 }
+    )]
+    #[case::k1_zero(
+        |vk: &mut VerificationKey| vk.k1 = Fr::zero(),
+        VerificationKeyValidationError::InvalidCosetSeparator { field: "k1" }
+    )]
+    #[case::k1_in_the_domain(
+        |vk: &mut VerificationKey| vk.k1 = vk.w,
+        VerificationKeyValidationError::InvalidCosetSeparator { field: "k1" }
+    )]
+    #[case::k2_in_the_k1_coset(
+        |vk: &mut VerificationKey| vk.k2 = vk.k1 * vk.w,
+        VerificationKeyValidationError::InvalidCosetSeparator { field: "k2" }
+    )]
+    #[case::w_not_primitive(
+        |vk: &mut VerificationKey| vk.w = vk.w * vk.w,
+        VerificationKeyValidationError::NotPrimitiveRoot { field: "w", order: 1 << 24 }
+    )]
+    #[case::one_point_domain(
+        |vk: &mut VerificationKey| {
+            vk.power = 0;
+            vk.w = Fr::one();
+            vk.wr = Fr::one();
+        },
+        VerificationKeyValidationError::NotPrimitiveRoot { field: "w", order: 1 }
+    )]
+    #[case::w_of_another_order(
+        |vk: &mut VerificationKey| vk.power = 23,
+        VerificationKeyValidationError::NotPrimitiveRoot { field: "w", order: 1 << 23 }
+    )]
+    #[case::w3(
+        |vk: &mut VerificationKey| vk.w3 = Fr::one(),
+        VerificationKeyValidationError::NotPrimitiveRoot { field: "w3", order: 3 }
+    )]
+    #[case::w4(
+        |vk: &mut VerificationKey| vk.w4 = vk.w4 * vk.w4,
+        VerificationKeyValidationError::NotPrimitiveRoot { field: "w4", order: 4 }
+    )]
+    #[case::w8(
+        |vk: &mut VerificationKey| vk.w8 = vk.w8 * vk.w8 * vk.w8 * vk.w8,
+        VerificationKeyValidationError::NotPrimitiveRoot { field: "w8", order: 8 }
+    )]
+    #[case::wr_not_a_cube_root(
+        |vk: &mut VerificationKey| vk.wr = vk.wr + Fr::one(),
+        VerificationKeyValidationError::InvalidShift
+    )]
+    #[case::c0(
+        |vk: &mut VerificationKey| vk.c0 = G1::zero(),
+        VerificationKeyValidationError::PointAtInfinity { field: "c0" }
+    )]
+    #[case::x2(
+        |vk: &mut VerificationKey| vk.x2 = G2::zero(),
+        VerificationKeyValidationError::PointAtInfinity { field: "x2" }
+    )]
+    fn reject_inconsistent_keys(
+        #[case] change: fn(&mut VerificationKey),
+        #[case] expected: VerificationKeyValidationError,
+    ) {
+        let mut vk = VerificationKey::default();
+        change(&mut vk);
+
+        assert_eq!(Err(expected), vk.validate());
+    }
+
+    #[test]
+    fn accept_just_the_canonical_bytes() {
+        // The first and the last byte of every word: the `X_2` subgroup check is slow
+        for pos in (0..DEFAULT_KEY_RAW_DATA.len()).filter(|pos| pos % 32 == 0 || pos % 32 == 31) {
+            for value in [0x00, 0xff, DEFAULT_KEY_RAW_DATA[pos] ^ 0x01] {
+                let mut data = DEFAULT_KEY_RAW_DATA;
+                data[pos] = value;
+
+                if let Ok(vk) = <VerificationKey>::from_bytes(&data) {
+                    assert_eq!(data.as_slice(), vk.to_bytes(), "Byte {pos} = {value:#x}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encode_projective_points_in_affine_coordinates() {
+        let mut vk = VerificationKey::default();
+        vk.c0 = vk.c0 * Fr::from_str("7").unwrap() - vk.c0 * Fr::from_str("6").unwrap();
+        vk.x2 = vk.x2 * Fr::from_str("7").unwrap() - vk.x2 * Fr::from_str("6").unwrap();
+
+        assert_eq!(DEFAULT_KEY_RAW_DATA, VerificationKeyRawData::from(&vk));
+    }
+
+    #[test]
+    fn encode_and_decode_points_at_infinity() {
+        let vk = VerificationKey {
+            c0: G1::zero(),
+            x2: G2::zero(),
+            ..Default::default()
+        };
+
+        let data = VerificationKeyRawData::from(&vk);
+
+        assert!(data[32 * 9..].iter().all(|b| *b == 0));
+        assert_eq!(vk, VerificationKey::try_from(&data).unwrap());
+    }
+
+    #[rstest]
+    #[should_panic(expected = "InvalidLength")]
+    #[case::too_short(&DEFAULT_KEY_RAW_DATA[..VERIFICATION_KEY_RAW_DATA_LEN - 1])]
+    #[should_panic(expected = "InvalidLength")]
+    #[case::empty(&[])]
+    fn reject_data_with_invalid_length(#[case] data: &[u8]) {
+        VerificationKey::<Bn254>::try_from(data).unwrap();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "InvalidPower")]
+    #[case::power_too_big(0, U256::from(29))]
+    #[should_panic(expected = "InvalidPower")]
+    #[case::power_not_a_byte(0, U256::from(0x118))]
+    #[case::max_power(0, U256::from(28))]
+    #[should_panic(expected = "InvalidPublicInputsNumber")]
+    #[case::too_many_public_inputs(1, U256::from(u32::MAX as u64 + 1))]
+    #[should_panic(expected = r#""k1", error: NotMember"#)]
+    #[case::fr_modulus(2, u256!("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"))]
+    #[should_panic(expected = r#""C0", error: NotMember"#)]
+    #[case::fq_modulus(10, u256!("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47"))]
+    #[should_panic(expected = r#""C0", error: NotOnCurve"#)]
+    #[case::c0_not_on_curve(10, U256::from(1))]
+    #[should_panic(expected = r#""X_2", error: NotOnCurve"#)]
+    #[case::x2_not_on_curve(11, U256::from(1))]
+    fn reject_invalid_key_data(#[case] word: usize, #[case] value: U256) {
+        let mut data = DEFAULT_KEY_RAW_DATA;
+        data[32 * word..32 * (word + 1)].copy_from_slice(&value.into_bytes());
+
+        VerificationKey::try_from(&data).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = r#""X_2", error: NotInSubgroup"#)]
+    fn reject_x2_not_in_the_subgroup() {
+        // A point on the twist curve y^2 = x^3 + 3/(9 + i) that is not in the r-torsion subgroup
+        let b = Fq2::new(
+            Fq::from_str(
+                "19485874751759354771024239261021720505790618469301721065564631296452457478373",
+            )
+            .unwrap(),
+            Fq::from_str(
+                "266929791119991161246907387137283842545076965332900288569378510910307636690",
+            )
+            .unwrap(),
+        );
+        let x = (1..)
+            .map(|i| {
+                Fq2::new(
+                    Fq::from_str("1").unwrap(),
+                    Fq::from_u256(U256::from(i)).unwrap(),
+                )
+            })
+            .find(|x| (*x * *x * *x + b).sqrt().is_some())
+            .unwrap();
+        let y = (x * x * x + b).sqrt().unwrap();
+        let mut data = DEFAULT_KEY_RAW_DATA;
+        for (word, value) in [x.imaginary(), x.real(), y.imaginary(), y.real()]
+            .into_iter()
+            .enumerate()
+        {
+            data[32 * (11 + word)..32 * (12 + word)]
+                .copy_from_slice(&value.into_u256().into_bytes());
+        }
+
+        VerificationKey::try_from(&data).unwrap();
+    }
+}
@@ -17,50 +17,134 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
-use macros::u256;
+extern crate alloc;
+
+use alloc::vec::Vec;
 use snafu::Snafu;
-use substrate_bn::{arith::U256, pairing_batch, AffineG1, AffineG2, Fq, Fq2, Fr, Gt, G1, G2};
+use substrate_bn::arith::U256;
 
-use hash::Hasher as _;
-use utils::IntoFq as _;
-use utils::IntoFr as _;
+use utils::IntoBytes as _;
 
+mod accumulator;
+mod batch;
+mod calldata;
+mod curve;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub(crate) mod hash;
 mod key;
 mod macros;
 mod proof;
+mod scale;
 pub(crate) mod serde;
+mod solidity;
+mod trace;
+mod transcript;
 pub(crate) mod utils;
-
-pub use proof::Proof;
+mod zkey;
+
+pub use accumulator::{verify_deferred, PairingAccumulator, PairingAccumulatorError};
+pub use batch::{verify_batch, verify_many, BatchVerifyError};
+pub use calldata::{verify_proof_selector, CalldataError};
+#[cfg(feature = "arkworks")]
+pub use curve::ArkBn254;
+pub use curve::{Bn254, Curve, Field, GroupError, Point, PointError};
+#[cfg(feature = "constant-time")]
+pub use curve::{CtBn254, CtFr, CtG1, CtG2};
+pub use proof::{Proof, ProofDataError, ProofWithoutInverse};
 
 /// The verification key.
 pub use key::VerificationKey;
+/// The verification key identifier.
+pub use key::VkId;
+/// The verification key as fixed size bytes array.
+pub use key::{VerificationKeyDataError, VerificationKeyRawData, VERIFICATION_KEY_RAW_DATA_LEN};
+pub use key::{VerificationKeyError, VerificationKeyValidationError, CURVE, PROTOCOL};
 /// The proof data as `U256` fixed array.
 pub use proof::ProofData;
 /// The proof data as fixed size bytes array.
 pub use proof::ProofRawData;
+/// The proof data without the batched inverse as fixed size bytes array.
+pub use proof::ProofRawDataWithoutInverse;
+#[cfg(feature = "serde")]
+pub use serde::Strict;
+#[cfg(feature = "solidity")]
+pub use solidity::solidity_verifier;
+pub use trace::{verify_with_trace, VerificationTrace};
+#[cfg(feature = "poseidon")]
+pub use transcript::{poseidon, PoseidonTranscript, POSEIDON_MAX_INPUTS};
+pub use transcript::{Keccak256Transcript, Transcript};
+#[cfg(feature = "zkey")]
+pub use zkey::ZkeyError;
+
+/// The public inputs data: a big-endian `U256` for each circuit's public signal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Public(Vec<U256>);
+
+impl Public {
+    /// The number of public inputs.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
 
-/// The public input data.
-pub struct Public(U256);
+    /// Return `true` if there are no public inputs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The public inputs values.
+    pub fn as_slice(&self) -> &[U256] {
+        &self.0
+    }
+}
 
 impl From<U256> for Public {
     fn from(inner: U256) -> Self {
+        Self(alloc::vec![inner])
+    }
+}
+
+impl From<Vec<U256>> for Public {
+    fn from(inner: Vec<U256>) -> Self {
         Self(inner)
     }
 }
 
+impl From<&[U256]> for Public {
+    fn from(inner: &[U256]) -> Self {
+        Self(inner.to_vec())
+    }
+}
+
+impl<const N: usize> From<[U256; N]> for Public {
+    fn from(inner: [U256; N]) -> Self {
+        Self(inner.to_vec())
+    }
+}
+
+impl FromIterator<U256> for Public {
+    fn from_iter<T: IntoIterator<Item = U256>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 impl From<[u8; 32]> for Public {
     fn from(inner: [u8; 32]) -> Self {
-        Self(U256::from_slice(&inner).expect("Cannot fails: is a 32-byte array"))
+        Self::from(U256::from_slice(&inner).expect("Cannot fails: is a 32-byte array"))
     }
 }
 
+/// Read the public inputs from the concatenation of 32-byte big-endian values: fails
+/// if the slice length is not a multiple of 32.
 impl TryFrom<&[u8]> for Public {
     type Error = core::array::TryFromSliceError;
 
     fn try_from(inner: &[u8]) -> Result<Self, Self::Error> {
-        <[u8; 32]>::try_from(inner).map(Into::into)
+        let chunks = inner.chunks_exact(32);
+        <[u8; 0]>::try_from(chunks.remainder())?;
+        Ok(chunks
+            .map(|chunk| U256::from_slice(chunk).expect("Cannot fails: is a 32-byte chunk"))
+            .collect())
     }
 }
 
@@ -71,7 +155,24 @@ pub enum VerifyError {
     #[snafu(display(
         "Invalid provided inverse is {inverse:?} that's not the inverse of {computed:?}"
     ))]
-    InvalidInverse { inverse: Fr, computed: Fr },
+    InvalidInverse { inverse: U256, computed: U256 },
+    /// The number of public inputs doesn't match the verification key one
+    #[snafu(display(
+        "Invalid public inputs number: the key expects {expected} inputs but {actual} are given"
+    ))]
+    InvalidPublicInputsNumber { expected: usize, actual: usize },
+    /// The public input at the given position is not a scalar field element
+    #[snafu(display("Invalid public input {index}: {value:?} is not a field element"))]
+    InvalidPublicInput { index: usize, value: U256 },
+    /// A proof's commitment is the point at infinity
+    #[snafu(display("Invalid proof: '{field}' is the point at infinity"))]
+    PointAtInfinity { field: &'static str },
+    /// A challenge has a value that makes the verifier equations degenerate
+    #[snafu(display("Degenerate challenge '{challenge}'"))]
+    DegenerateChallenge { challenge: &'static str },
+    /// The verification key is malformed
+    #[snafu(display("Invalid verification key '{field}'"))]
+    InvalidVerificationKey { field: &'static str },
     /// Cannot verify the pairing for this proof
     #[snafu(display("Cannot verify paring"))]
     NotPairing,
@@ -79,88 +180,253 @@ pub enum VerifyError {
 
 /// Use the given verification key `vk` to verify the `proof`` against the given `pubs` public inputs.
 /// Can fail if:
+/// - the verification key is malformed
+/// - the number of public inputs is not the one declared in the verification key
+/// - some public input is not a scalar field element
+/// - some proof's commitment is the point at infinity
+/// - some challenge is degenerate
 /// - the provided inverse in the proof is wrong
 /// - the pair checking is wrong
-pub fn verify(vk: &VerificationKey, proof: &Proof, pubs: &Public) -> Result<(), VerifyError> {
-    let vk_data = vk.into();
-    let challenges = Challenges::build(&vk_data, proof, pubs);
-    let (inverse, l1) = challenges.compute_inverse(&vk_data, proof.evaluations.inv)?;
-    let pi = Proof::compute_pi(pubs, l1);
-    let r0 = proof.compute_r0(&challenges, &inverse.li_s0_inv);
-    let r1 = proof.compute_r1(&challenges, pi, inverse.zh_inv, &inverse.li_s1_inv);
-    let r2 = proof.compute_r2(
-        &vk_data,
-        &challenges,
-        l1,
-        inverse.zh_inv,
-        &inverse.li_s2_inv,
-    );
-
-    let (f, e, j) = proof.compute_fej(
-        vk_data.vk,
-        &challenges,
-        r0,
-        r1,
-        r2,
-        inverse.den_h1,
-        inverse.den_h2,
-    );
-
-    proof.check_paring(&challenges, vk_data.vk, f, e, j)
+pub fn verify<C: Curve>(
+    vk: &VerificationKey<C>,
+    proof: &Proof<C>,
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    verify_prepared(&vk.into(), proof, pubs)
+}
+
+/// Like [`verify`] but use a [`PreparedVerificationKey`]: all the values that depend just on
+/// the verification key are computed once when you prepare it and not for every proof.
+pub fn verify_prepared<C: Curve>(
+    vk: &PreparedVerificationKey<C>,
+    proof: &Proof<C>,
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    verify_with_transcript::<Keccak256Transcript<C>>(vk, proof, pubs)
+}
+
+/// Like [`verify_prepared`] but derive the challenges with the `T` transcript instead of the
+/// snarkjs' [`Keccak256Transcript`] one: e.g. for proofs built for in-circuit recursion.
+pub fn verify_with_transcript<T: Transcript>(
+    vk: &PreparedVerificationKey<T::Curve>,
+    proof: &Proof<T::Curve>,
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    let (challenges, (f, e, j)) = proof.compute_challenges_and_fej::<T>(vk, pubs)?;
+
+    proof.check_paring(&challenges, vk, f, e, j)
+}
+
+/// Like [`verify`] but for a [`ProofWithoutInverse`]: compute the batched inverse of the
+/// challenges' denominators instead of checking the one provided by the prover.
+pub fn verify_without_inverse<C: Curve>(
+    vk: &VerificationKey<C>,
+    proof: &ProofWithoutInverse<C>,
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    let vk = PreparedVerificationKey::from(vk);
+    let proof = proof.as_proof();
+    let (challenges, trace) =
+        proof.compute_trace_with::<Keccak256Transcript<C>>(&vk, pubs, None)?;
+
+    proof.check_paring(&challenges, &vk, trace.f, trace.e, trace.j)
 }
 
-struct VkData<'a> {
-    vk: &'a VerificationKey,
-    precomputed: PrecomputedData,
+/// A verification key with all its derived values already computed: the roots powers,
+/// the domain size, the normalized (affine) `C0` and `X_2` points, the curve generators
+/// and the `X_2` and G2 generator line coefficients ([`Curve::G2Prepared`]). Build it once
+/// and use it with [`verify_prepared`] to verify many proofs.
+///
+/// Note that `substrate-bn` doesn't expose the G2 line coefficients precomputation, so
+/// the [`Bn254`] (and `CtBn254`) Miller loop still computes them at every pairing: the
+/// `ArkBn254` one uses the cached coefficients.
+#[derive(Clone, Debug)]
+pub struct PreparedVerificationKey<C: Curve = Bn254> {
+    vk: VerificationKey<C>,
+    precomputed: PrecomputedData<C>,
+    invalid_field: Option<&'static str>,
 }
 
-impl<'a> From<&'a VerificationKey> for VkData<'a> {
-    fn from(vk: &'a VerificationKey) -> Self {
+impl<C: Curve> PreparedVerificationKey<C> {
+    /// The verification key.
+    pub fn vk(&self) -> &VerificationKey<C> {
+        &self.vk
+    }
+}
+
+impl<C: Curve> From<VerificationKey<C>> for PreparedVerificationKey<C> {
+    fn from(vk: VerificationKey<C>) -> Self {
+        let precomputed = PrecomputedData::from(&vk);
         Self {
+            invalid_field: vk.validate().err().map(|error| error.field()),
+            precomputed,
             vk,
-            precomputed: vk.into(),
         }
     }
 }
 
-struct PrecomputedData {
-    pub n: Fr,
-    pub w3: [Fr; 2],
-    pub w4: [Fr; 3],
-    pub w8: [Fr; 7],
+impl<C: Curve> From<&VerificationKey<C>> for PreparedVerificationKey<C> {
+    fn from(vk: &VerificationKey<C>) -> Self {
+        vk.clone().into()
+    }
 }
 
-impl From<&VerificationKey> for PrecomputedData {
-    fn from(vk: &VerificationKey) -> Self {
+#[derive(Clone, Debug)]
+struct PrecomputedData<C: Curve> {
+    pub n: C::Fr,
+    pub w3: [C::Fr; 2],
+    pub w4: [C::Fr; 3],
+    pub w8: [C::Fr; 7],
+    pub c0: C::G1,
+    pub x2: C::G2,
+    pub g1: C::G1,
+    pub x2_prepared: C::G2Prepared,
+    pub g2_prepared: C::G2Prepared,
+}
+
+impl<C: Curve> From<&VerificationKey<C>> for PrecomputedData<C> {
+    fn from(vk: &VerificationKey<C>) -> Self {
         let w3 = [vk.w3, vk.w3 * vk.w3];
         let w4_2 = vk.w4 * vk.w4;
         let w4 = [vk.w4, w4_2, vk.w4 * w4_2];
-        let mut w8: [Fr; 7] = [Fr::zero(); 7];
+        let mut w8 = [C::Fr::zero(); 7];
         w8[0] = vk.w8;
         for i in 1..7 {
             w8[i] = w8[i - 1] * vk.w8;
         }
+        let mut c0 = vk.c0;
+        c0.normalize();
+        let mut x2 = vk.x2;
+        x2.normalize();
         Self {
-            n: 2.into_fr().pow((vk.power as u64).into_fr()),
+            n: C::Fr::from_u64(2).pow(vk.power as u64),
             w3,
             w4,
             w8,
+            c0,
+            x2,
+            g1: C::G1::generator(),
+            x2_prepared: C::prepare(&x2),
+            g2_prepared: C::prepare(&C::G2::generator()),
         }
     }
 }
 
-impl Proof {
-    /// Compute public input polynomial evaluation PI(xi)
-    fn compute_pi(p: &Public, l1: Fr) -> Fr {
-        -l1 * p.0.into_fr()
+impl<C: Curve> Proof<C> {
+    /// Execute all the verification steps that precede the pairing check: compute the
+    /// challenges and the `F`, `E` and `J` points.
+    #[allow(clippy::type_complexity)]
+    fn compute_challenges_and_fej<T: Transcript<Curve = C>>(
+        &self,
+        vk: &PreparedVerificationKey<C>,
+        pubs: &Public,
+    ) -> Result<(Challenges<C>, (C::G1, C::G1, C::G1)), VerifyError> {
+        self.compute_trace::<T>(vk, pubs)
+            .map(|(challenges, trace)| (challenges, (trace.f, trace.e, trace.j)))
+    }
+
+    /// Like [`Proof::compute_challenges_and_fej`] but return all the intermediate values: the
+    /// trace's points are not normalized.
+    fn compute_trace<T: Transcript<Curve = C>>(
+        &self,
+        vk: &PreparedVerificationKey<C>,
+        pubs: &Public,
+    ) -> Result<(Challenges<C>, VerificationTrace<C>), VerifyError> {
+        self.compute_trace_with::<T>(vk, pubs, Some(self.evaluations.inv))
+    }
+
+    /// Like [`Proof::compute_trace`] but check the `provided` batched inverse or, if it's
+    /// `None`, compute it.
+    fn compute_trace_with<T: Transcript<Curve = C>>(
+        &self,
+        vk: &PreparedVerificationKey<C>,
+        pubs: &Public,
+        provided: Option<C::Fr>,
+    ) -> Result<(Challenges<C>, VerificationTrace<C>), VerifyError> {
+        if let Some(field) = vk.invalid_field {
+            return Err(VerifyError::InvalidVerificationKey { field });
+        }
+        if pubs.len() != vk.vk.n_public {
+            return Err(VerifyError::InvalidPublicInputsNumber {
+                expected: vk.vk.n_public,
+                actual: pubs.len(),
+            });
+        }
+        if let Some((index, value)) = pubs
+            .0
+            .iter()
+            .enumerate()
+            .find(|(_, value)| C::Fr::from_be_bytes(&value.into_bytes()).is_none())
+        {
+            return Err(VerifyError::InvalidPublicInput {
+                index,
+                value: *value,
+            });
+        }
+        self.check_points()?;
+        let challenges = Challenges::build::<T>(vk, self, pubs);
+        challenges.check_degenerate()?;
+        let (inverse, lagrange) = challenges.compute_inverse(vk, vk.vk.n_public, provided)?;
+        let l1 = lagrange[0];
+        let pi = Self::compute_pi(pubs, &lagrange);
+        let r0 = self.compute_r0(&challenges, &inverse.li_s0_inv);
+        let r1 = self.compute_r1(&challenges, pi, inverse.zh_inv, &inverse.li_s1_inv);
+        let r2 = self.compute_r2(vk, &challenges, l1, inverse.zh_inv, &inverse.li_s2_inv);
+
+        let (f, e, j) =
+            self.compute_fej(vk, &challenges, r0, r1, r2, inverse.den_h1, inverse.den_h2);
+        let trace = VerificationTrace {
+            beta: challenges.beta,
+            gamma: challenges.gamma,
+            xi: challenges.xi,
+            alpha: challenges.alpha,
+            y: challenges.y,
+            zh: challenges.zh,
+            l1,
+            pi,
+            r0,
+            r1,
+            r2,
+            f,
+            e,
+            j,
+        };
+        Ok((challenges, trace))
+    }
+
+    /// Check that no commitment is the point at infinity.
+    fn check_points(&self) -> Result<(), VerifyError> {
+        use proof::ProofFields::*;
+        let polynomials = &self.polynomials;
+        [
+            (C1, polynomials.c1),
+            (C2, polynomials.c2),
+            (W1, polynomials.w1),
+            (W2, polynomials.w2),
+        ]
+        .into_iter()
+        .find(|(_, point)| point.is_zero())
+        .map_or(Ok(()), |(field, _)| {
+            Err(VerifyError::PointAtInfinity { field: field.str() })
+        })
+    }
+
+    /// Compute public input polynomial evaluation PI(xi) = -sum_i(L_i(xi) * pub_i)
+    fn compute_pi(pubs: &Public, lagrange: &[C::Fr]) -> C::Fr {
+        pubs.0
+            .iter()
+            .zip(lagrange)
+            .fold(C::Fr::zero(), |acc, (p, l)| {
+                acc - *l * C::Fr::from_be_bytes_reduced(&p.into_bytes())
+            })
     }
 
     /// Compute r0(y) by interpolating the polynomial r0(X) using 8 points (x,y)
     /// where x = {h9, h0w8, h0w8^2, h0w8^3, h0w8^4, h0w8^5, h0w8^6, h0w8^7}
     /// and   y = {C0(h0), C0(h0w8), C0(h0w8^2), C0(h0w8^3), C0(h0w8^4), C0(h0w8^5), C0(h0w8^6), C0(h0w8^7)}
     /// and computing C0(xi)
-    fn compute_r0(&self, challenges: &Challenges, li_s0_inv: &LiS0) -> Fr {
-        let base = challenges.y.pow(8_u64.into_fr()) - challenges.xi;
+    fn compute_r0(&self, challenges: &Challenges<C>, li_s0_inv: &LiS0<C::Fr>) -> C::Fr {
+        let base = challenges.y.pow(8) - challenges.xi;
         let evaluations = &self.evaluations;
 
         let coefficients = [
@@ -183,8 +449,14 @@ impl Proof {
     /// where x = {h1, h1w4, h1w4^2, h1w4^3}
     /// and   y = {C1(h1), C1(h1w4), C1(h1w4^2), C1(h1w4^3)}
     /// and computing T0(xi)
-    fn compute_r1(&self, challenges: &Challenges, pi: Fr, zh_inv: Fr, li_s1_inv: &LiS1) -> Fr {
-        let base = challenges.y.pow(4_u64.into_fr()) - challenges.xi;
+    fn compute_r1(
+        &self,
+        challenges: &Challenges<C>,
+        pi: C::Fr,
+        zh_inv: C::Fr,
+        li_s1_inv: &LiS1<C::Fr>,
+    ) -> C::Fr {
+        let base = challenges.y.pow(4) - challenges.xi;
         let evaluations = &self.evaluations;
 
         let t0 = ((evaluations.ql * evaluations.a)
@@ -205,19 +477,19 @@ impl Proof {
     /// and computing T1(xi) and T2(xi)
     fn compute_r2(
         &self,
-        vk: &VkData,
-        challenges: &Challenges,
-        l1: Fr,
-        zh_inv: Fr,
-        li_s2_inv: &LiS2,
-    ) -> Fr {
-        let base = challenges.y.pow(6_u64.into_fr())
-            - (challenges.y.pow(3_u64.into_fr()) * challenges.xi * (Fr::one() + vk.vk.w))
+        vk: &PreparedVerificationKey<C>,
+        challenges: &Challenges<C>,
+        l1: C::Fr,
+        zh_inv: C::Fr,
+        li_s2_inv: &LiS2<C::Fr>,
+    ) -> C::Fr {
+        let base = challenges.y.pow(6)
+            - (challenges.y.pow(3) * challenges.xi * (C::Fr::one() + vk.vk.w))
             + (challenges.xi * challenges.xi * vk.vk.w);
         let evaluations = &self.evaluations;
 
         let beta_xi = challenges.beta * challenges.xi;
-        let t1 = (evaluations.z - Fr::one()) * l1 * zh_inv;
+        let t1 = (evaluations.z - C::Fr::one()) * l1 * zh_inv;
         let t2 = (((evaluations.a + beta_xi + challenges.gamma)
             * (evaluations.b + beta_xi * vk.vk.k1 + challenges.gamma)
             * (evaluations.c + beta_xi * vk.vk.k2 + challenges.gamma)
@@ -250,23 +522,23 @@ impl Proof {
     #[allow(clippy::too_many_arguments)]
     fn compute_fej(
         &self,
-        vk: &VerificationKey,
-        challenges: &Challenges,
-        r0: Fr,
-        r1: Fr,
-        r2: Fr,
-        den_h1: Fr,
-        den_h2: Fr,
-    ) -> (G1, G1, G1) {
+        vk: &PreparedVerificationKey<C>,
+        challenges: &Challenges<C>,
+        r0: C::Fr,
+        r1: C::Fr,
+        r2: C::Fr,
+        den_h1: C::Fr,
+        den_h2: C::Fr,
+    ) -> (C::G1, C::G1, C::G1) {
         let polynomials = &self.polynomials;
         let numerator = challenges
             .h0_w8
             .iter()
-            .fold(Fr::one(), |acc, h0_w8_i| acc * (challenges.y - *h0_w8_i));
+            .fold(C::Fr::one(), |acc, h0_w8_i| acc * (challenges.y - *h0_w8_i));
         let quotient1 = challenges.alpha * numerator * den_h1;
         let quotient2 = challenges.alpha * challenges.alpha * numerator * den_h2;
-        let f = polynomials.c1 * quotient1 + polynomials.c2 * quotient2 + vk.c0;
-        let e = Challenges::g1() * (r0 + quotient1 * r1 + quotient2 * r2);
+        let f = polynomials.c1 * quotient1 + polynomials.c2 * quotient2 + vk.precomputed.c0;
+        let e = vk.precomputed.g1 * (r0 + quotient1 * r1 + quotient2 * r2);
         let j = polynomials.w1 * numerator;
 
         (f, e, j)
@@ -274,33 +546,59 @@ impl Proof {
 
     fn check_paring(
         &self,
-        challenges: &Challenges,
-        vk: &VerificationKey,
-        f: G1,
-        e: G1,
-        j: G1,
+        challenges: &Challenges<C>,
+        vk: &PreparedVerificationKey<C>,
+        f: C::G1,
+        e: C::G1,
+        j: C::G1,
     ) -> Result<(), VerifyError> {
-        let polynomials = &self.polynomials;
-        let f = f - e - j + polynomials.w2 * challenges.y;
-        if pairing_batch(&[(f, Challenges::g2_pair()), (-polynomials.w2, vk.x2)]) == Gt::one() {
-            Ok(())
-        } else {
-            Err(VerifyError::NotPairing)
-        }
+        check_paring(
+            self.compute_pairing_lhs(challenges, f, e, j),
+            self.polynomials.w2,
+            vk,
+        )
+    }
+
+    /// Compute the `F - E - J + y·W2` point that should be paired with the G2 generator.
+    fn compute_pairing_lhs(
+        &self,
+        challenges: &Challenges<C>,
+        f: C::G1,
+        e: C::G1,
+        j: C::G1,
+    ) -> C::G1 {
+        f - e - j + self.polynomials.w2 * challenges.y
     }
 }
 
-fn polynomial_eval(
-    base: Fr,
-    coefficients: &[Fr],
-    challenges: &[Fr],
-    inv: &[Fr],
-    acc: Option<Fr>,
-) -> Fr {
-    let mut acc = acc.unwrap_or(Fr::zero());
+/// Check that `e(lhs, G2) · e(-w2, X2) == 1`.
+fn check_paring<C: Curve>(
+    lhs: C::G1,
+    w2: C::G1,
+    vk: &PreparedVerificationKey<C>,
+) -> Result<(), VerifyError> {
+    let precomputed = &vk.precomputed;
+    if C::prepared_pairing_check(&[
+        (lhs, &precomputed.g2_prepared),
+        (-w2, &precomputed.x2_prepared),
+    ]) {
+        Ok(())
+    } else {
+        Err(VerifyError::NotPairing)
+    }
+}
+
+fn polynomial_eval<F: Field>(
+    base: F,
+    coefficients: &[F],
+    challenges: &[F],
+    inv: &[F],
+    acc: Option<F>,
+) -> F {
+    let mut acc = acc.unwrap_or(F::zero());
     for (i, root) in challenges.iter().enumerate() {
-        let mut h = Fr::one();
-        let mut c1_value = Fr::zero();
+        let mut h = F::one();
+        let mut c1_value = F::zero();
         for c in coefficients {
             c1_value = c1_value + (*c) * h;
             h = h * *root;
@@ -310,83 +608,61 @@ fn polynomial_eval(
     acc
 }
 
-trait FFlonkConstants {
-    fn g1() -> G1 {
-        AffineG1::new(Fq::one(), 2.into_fq())
-            .expect("(1, 2) Should be a valid G1 point")
-            .into()
-    }
-
-    const G2_X1: U256 = u256!("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed");
-    const G2_X2: U256 = u256!("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2");
-    const G2_Y1: U256 = u256!("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa");
-    const G2_Y2: U256 = u256!("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b");
-
-    fn g2_pair() -> G2 {
-        let g2x1 = Fq::from_u256(Self::G2_X1).expect("G2x1 should be a valid Fq point");
-        let g2x2 = Fq::from_u256(Self::G2_X2).expect("G2x2 should be a valid Fq point");
-        let g2y1 = Fq::from_u256(Self::G2_Y1).expect("G2y1 should be a valid Fq point");
-        let g2y2 = Fq::from_u256(Self::G2_Y2).expect("G2y2 should be a valid Fq point");
-        AffineG2::new(Fq2::new(g2x1, g2x2), Fq2::new(g2y1, g2y2))
-            .expect("Should be on curve")
-            .into()
-    }
-}
-
 #[derive(Debug)]
-struct Challenges {
-    beta: Fr,
-    gamma: Fr,
-    h0_w8: [Fr; 8],
-    h1_w4: [Fr; 4],
-    h2_w3: [Fr; 3],
-    h3_w3: [Fr; 3],
-    xi: Fr,
-    zh: Fr,
-    alpha: Fr,
-    y: Fr,
+struct Challenges<C: Curve> {
+    beta: C::Fr,
+    gamma: C::Fr,
+    h0_w8: [C::Fr; 8],
+    h1_w4: [C::Fr; 4],
+    h2_w3: [C::Fr; 3],
+    h3_w3: [C::Fr; 3],
+    xi: C::Fr,
+    zh: C::Fr,
+    alpha: C::Fr,
+    y: C::Fr,
 }
 
-type LiS0 = [Fr; 8];
-type LiS1 = [Fr; 4];
-type LiS2 = [Fr; 6];
+type LiS0<F> = [F; 8];
+type LiS1<F> = [F; 4];
+type LiS2<F> = [F; 6];
 
 #[derive(Debug, PartialEq)]
-struct Inverse {
-    li_s0_inv: LiS0,
-    li_s1_inv: LiS1,
-    li_s2_inv: LiS2,
-    den_h1: Fr,
-    den_h2: Fr,
-    zh_inv: Fr,
+struct Inverse<F> {
+    li_s0_inv: LiS0<F>,
+    li_s1_inv: LiS1<F>,
+    li_s2_inv: LiS2<F>,
+    den_h1: F,
+    den_h2: F,
+    zh_inv: F,
 }
 
-impl Challenges {
-    fn build(vk: &VkData, proof: &Proof, public: &Public) -> Self {
+impl<C: Curve> Challenges<C> {
+    fn build<T: Transcript<Curve = C>>(
+        vk: &PreparedVerificationKey<C>,
+        proof: &Proof<C>,
+        public: &Public,
+    ) -> Self {
         let precomputed = &vk.precomputed;
-        let vk = vk.vk;
+        let vk = &vk.vk;
         let Proof {
             ref polynomials,
             ref evaluations,
         } = proof;
+        let mut transcript = T::default();
 
-        let beta = [
-            vk.c0.x().into_u256(),
-            vk.c0.y().into_u256(),
-            public.0,
-            polynomials.c1.x().into_u256(),
-            polynomials.c1.y().into_u256(),
-        ]
-        .hash()
-        .into_fr();
-        let gamma = [beta.into_u256()].hash().into_fr();
-        let xi_seed = [
-            gamma.into_u256(),
-            polynomials.c2.x().into_u256(),
-            polynomials.c2.y().into_u256(),
-        ]
-        .hash()
-        .into_fr();
+        transcript.absorb_g1(precomputed.c0);
+        for value in public.0.iter() {
+            transcript.absorb_scalar(C::Fr::from_be_bytes_reduced(&value.into_bytes()));
+        }
+        transcript.absorb_g1(polynomials.c1);
+        let beta = transcript.squeeze_challenge();
+
+        transcript.absorb_scalar(beta);
+        let gamma = transcript.squeeze_challenge();
+
+        transcript.absorb_scalar(gamma);
+        transcript.absorb_g1(polynomials.c2);
+        let xi_seed = transcript.squeeze_challenge();
         let xi_seed_2 = xi_seed * xi_seed;
         let xi_seed_3 = xi_seed * xi_seed_2;
         let h0_w8 = [
@@ -419,34 +695,33 @@ impl Challenges {
             h3_w3_0 * precomputed.w3[1],
         ];
         let xi = xi_seed_8 * xi_seed_8 * xi_seed_8;
-        let zh = xi.pow(precomputed.n) - Fr::one();
-        let alpha = [
-            xi_seed.into_u256(),
-            evaluations.ql.into_u256(),
-            evaluations.qr.into_u256(),
-            evaluations.qm.into_u256(),
-            evaluations.qo.into_u256(),
-            evaluations.qc.into_u256(),
-            evaluations.s1.into_u256(),
-            evaluations.s2.into_u256(),
-            evaluations.s3.into_u256(),
-            evaluations.a.into_u256(),
-            evaluations.b.into_u256(),
-            evaluations.c.into_u256(),
-            evaluations.z.into_u256(),
-            evaluations.zw.into_u256(),
-            evaluations.t1w.into_u256(),
-            evaluations.t2w.into_u256(),
-        ]
-        .hash()
-        .into_fr();
-        let y = [
-            alpha.into_u256(),
-            polynomials.w1.x().into_u256(),
-            polynomials.w1.y().into_u256(),
-        ]
-        .hash()
-        .into_fr();
+        // xi^n with n = 2^power
+        let zh = (0..vk.power).fold(xi, |acc, _| acc * acc) - C::Fr::one();
+        transcript.absorb_scalar(xi_seed);
+        for value in [
+            evaluations.ql,
+            evaluations.qr,
+            evaluations.qm,
+            evaluations.qo,
+            evaluations.qc,
+            evaluations.s1,
+            evaluations.s2,
+            evaluations.s3,
+            evaluations.a,
+            evaluations.b,
+            evaluations.c,
+            evaluations.z,
+            evaluations.zw,
+            evaluations.t1w,
+            evaluations.t2w,
+        ] {
+            transcript.absorb_scalar(value);
+        }
+        let alpha = transcript.squeeze_challenge();
+
+        transcript.absorb_scalar(alpha);
+        transcript.absorb_g1(polynomials.w1);
+        let y = transcript.squeeze_challenge();
         Self {
             beta,
             gamma,
@@ -461,8 +736,32 @@ impl Challenges {
         }
     }
 
-    fn compute_li_s0(&self) -> LiS0 {
-        let den1 = self.h0_w8[0].pow(6_u64.into_fr()) * 8_u64.into_fr();
+    /// Check that the challenges don't make any denominator vanish: `xi` should not be zero
+    /// or a domain root (`zh == 0`) and `y` should not be one of the opening points.
+    fn check_degenerate(&self) -> Result<(), VerifyError> {
+        let degenerate = if self.xi.is_zero() {
+            Some("xi")
+        } else if self.zh.is_zero() {
+            Some("zh")
+        } else if self
+            .h0_w8
+            .iter()
+            .chain(&self.h1_w4)
+            .chain(&self.h2_w3)
+            .chain(&self.h3_w3)
+            .any(|root| *root == self.y)
+        {
+            Some("y")
+        } else {
+            None
+        };
+        degenerate.map_or(Ok(()), |challenge| {
+            Err(VerifyError::DegenerateChallenge { challenge })
+        })
+    }
+
+    fn compute_li_s0(&self) -> LiS0<C::Fr> {
+        let den1 = self.h0_w8[0].pow(6) * C::Fr::from_u64(8);
         [
             den1 * self.h0_w8[0] * (self.y - self.h0_w8[0]),
             den1 * self.h0_w8[7] * (self.y - self.h0_w8[1]),
@@ -475,8 +774,8 @@ impl Challenges {
         ]
     }
 
-    fn compute_li_s1(&self) -> LiS1 {
-        let den1 = self.h1_w4[0] * self.h1_w4[0] * 4_u64.into_fr();
+    fn compute_li_s1(&self) -> LiS1<C::Fr> {
+        let den1 = self.h1_w4[0] * self.h1_w4[0] * C::Fr::from_u64(4);
         [
             den1 * self.h1_w4[0] * (self.y - self.h1_w4[0]),
             den1 * self.h1_w4[3] * (self.y - self.h1_w4[1]),
@@ -485,9 +784,9 @@ impl Challenges {
         ]
     }
 
-    fn compute_li_s2(&self, w: Fr) -> LiS2 {
-        let den1_0 = 3_u64.into_fr() * self.h2_w3[0] * (self.xi - self.xi * w);
-        let den1_1 = 3_u64.into_fr() * self.h3_w3[0] * (self.xi * w - self.xi);
+    fn compute_li_s2(&self, w: C::Fr) -> LiS2<C::Fr> {
+        let den1_0 = C::Fr::from_u64(3) * self.h2_w3[0] * (self.xi - self.xi * w);
+        let den1_1 = C::Fr::from_u64(3) * self.h3_w3[0] * (self.xi * w - self.xi);
         [
             den1_0 * self.h2_w3[0] * (self.y - self.h2_w3[0]),
             den1_0 * self.h2_w3[2] * (self.y - self.h2_w3[1]),
@@ -498,18 +797,18 @@ impl Challenges {
         ]
     }
 
-    fn compute_eval_l1_base(&self, n: Fr) -> Fr {
-        n * (self.xi - Fr::one())
+    fn compute_eval_l_base(&self, n: C::Fr, root: C::Fr) -> C::Fr {
+        n * (self.xi - root)
     }
 
-    fn compute_den_h1_base(&self) -> Fr {
+    fn compute_den_h1_base(&self) -> C::Fr {
         let w = self.y - self.h1_w4[0];
         let w = w * (self.y - self.h1_w4[1]);
         let w = w * (self.y - self.h1_w4[2]);
         w * (self.y - self.h1_w4[3])
     }
 
-    fn compute_den_h2_base(&self) -> Fr {
+    fn compute_den_h2_base(&self) -> C::Fr {
         let w = self.y - self.h2_w3[0];
         let w = w * (self.y - self.h2_w3[1]);
         let w = w * (self.y - self.h2_w3[2]);
@@ -518,59 +817,88 @@ impl Challenges {
         w * (self.y - self.h3_w3[2])
     }
 
-    fn compute_inverse(&self, vk: &VkData, expected: Fr) -> Result<(Inverse, Fr), VerifyError> {
+    /// Check the batched inverse provided by the prover (or compute it, if it's `None`) and
+    /// compute all the inverses: return them with the Lagrange polynomial evaluations
+    /// `L_i(xi)` for each public input (at least `L_1(xi)` that's always needed).
+    #[allow(clippy::type_complexity)]
+    fn compute_inverse(
+        &self,
+        vk: &PreparedVerificationKey<C>,
+        n_public: usize,
+        provided: Option<C::Fr>,
+    ) -> Result<(Inverse<C::Fr>, Vec<C::Fr>), VerifyError> {
         let den_h1_base = self.compute_den_h1_base();
         let den_h2_base = self.compute_den_h2_base();
-        let mut data = [Fr::zero(); 22];
-        data[0] = self.zh;
-        data[1] = data[0] * den_h1_base;
-        data[2] = data[1] * den_h2_base;
+        let roots = lagrange_roots(vk.vk.w, n_public.max(1));
+        let mut data = Vec::with_capacity(21 + roots.len());
+        data.push(self.zh);
+        data.push(data[0] * den_h1_base);
+        data.push(data[1] * den_h2_base);
         let mut cursor = 3;
         let li_s0 = self.compute_li_s0();
         for elem in li_s0 {
-            data[cursor] = data[cursor - 1] * elem;
+            data.push(data[cursor - 1] * elem);
             cursor += 1;
         }
         let li_s1 = self.compute_li_s1();
         for elem in li_s1 {
-            data[cursor] = data[cursor - 1] * elem;
+            data.push(data[cursor - 1] * elem);
             cursor += 1;
         }
         let li_s2 = self.compute_li_s2(vk.vk.w);
         for elem in li_s2 {
-            data[cursor] = data[cursor - 1] * elem;
+            data.push(data[cursor - 1] * elem);
             cursor += 1;
         }
-
-        let eval_l1_base = self.compute_eval_l1_base(vk.precomputed.n);
-        data[cursor] = data[cursor - 1] * eval_l1_base;
-        let value = data[cursor];
-
-        if Fr::one() != value * expected {
-            return Err(VerifyError::InvalidInverse {
-                inverse: expected,
-                computed: value,
-            });
+        let eval_l_bases = roots
+            .iter()
+            .map(|root| self.compute_eval_l_base(vk.precomputed.n, *root))
+            .collect::<Vec<_>>();
+        for elem in eval_l_bases.iter() {
+            data.push(data[cursor - 1] * *elem);
+            cursor += 1;
         }
-        data[cursor] = expected;
         cursor -= 1;
-        // We get l1 from batches and we compute the polynomial evaluation L1(x)
-        let l1 = data[cursor + 1] * data[cursor] * self.zh;
-        data[cursor] = data[cursor + 1] * eval_l1_base;
+        let value = data[cursor];
+
+        let inverse = match provided {
+            Some(inverse) if C::Fr::one() != value * inverse => {
+                return Err(VerifyError::InvalidInverse {
+                    inverse: to_u256(inverse),
+                    computed: to_u256(value),
+                })
+            }
+            Some(inverse) => inverse,
+            // The Montgomery's trick: a single inversion for all the denominators. On a
+            // one-point domain (`w = wr = 1`) the S2 opening points collapse and `xi` makes
+            // its Lagrange denominators vanish.
+            None => value
+                .inverse()
+                .ok_or(VerifyError::DegenerateChallenge { challenge: "xi" })?,
+        };
+        data[cursor] = inverse;
         cursor -= 1;
-        let mut li_s2_inv = [Fr::zero(); 6];
+        // We get the Lagrange bases inverse from batches and we compute the polynomial
+        // evaluations L_i(xi) = w^i * zh / (n * (xi - w^i))
+        let mut lagrange = alloc::vec![C::Fr::zero(); roots.len()];
+        for (pos, elem) in eval_l_bases.into_iter().enumerate().rev() {
+            lagrange[pos] = data[cursor + 1] * data[cursor] * roots[pos] * self.zh;
+            data[cursor] = data[cursor + 1] * elem;
+            cursor -= 1;
+        }
+        let mut li_s2_inv = [C::Fr::zero(); 6];
         for (pos, elem) in li_s2.into_iter().enumerate().rev() {
             li_s2_inv[pos] = data[cursor + 1] * data[cursor];
             data[cursor] = data[cursor + 1] * elem;
             cursor -= 1;
         }
-        let mut li_s1_inv = [Fr::zero(); 4];
+        let mut li_s1_inv = [C::Fr::zero(); 4];
         for (pos, elem) in li_s1.into_iter().enumerate().rev() {
             li_s1_inv[pos] = data[cursor + 1] * data[cursor];
             data[cursor] = data[cursor + 1] * elem;
             cursor -= 1;
         }
-        let mut li_s0_inv = [Fr::zero(); 8];
+        let mut li_s0_inv = [C::Fr::zero(); 8];
         for (pos, elem) in li_s0.into_iter().enumerate().rev() {
             li_s0_inv[pos] = data[cursor + 1] * data[cursor];
             data[cursor] = data[cursor + 1] * elem;
@@ -591,12 +919,22 @@ impl Challenges {
                 den_h2,
                 zh_inv,
             },
-            l1,
+            lagrange,
         ))
     }
 }
 
-impl FFlonkConstants for Challenges {}
+/// The first `count` powers of `w` starting from `w^0`: the roots where the Lagrange
+/// polynomials for the public inputs are evaluated.
+fn lagrange_roots<F: Field>(w: F, count: usize) -> Vec<F> {
+    core::iter::successors(Some(F::one()), |root| Some(*root * w))
+        .take(count)
+        .collect()
+}
+
+fn to_u256<F: Field>(value: F) -> U256 {
+    U256::from_slice(&value.to_be_bytes()).expect("Cannot fail: is a 32-byte array")
+}
 
 #[cfg(test)]
 mod should;
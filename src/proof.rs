@@ -14,76 +14,100 @@
 // You should have received a copy of the GNU General Public License
 // along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
 
+use alloc::vec::Vec;
 use snafu::Snafu;
-use substrate_bn::{arith::U256, AffineG1, FieldError, Fq, Fr, GroupError, G1};
+use substrate_bn::{arith::U256, FieldError};
 
-use crate::utils::IntoBytes;
+use crate::{
+    curve::{Bn254, Curve, Field, GroupError, Point, PointError},
+    utils::IntoBytes,
+};
 
+/// The BN254 proof as 24 words: the `bytes32[24]` of the snarkjs' Solidity verifier.
 #[derive(Clone, PartialEq, Debug)]
 pub struct ProofData([U256; 24]);
 pub type ProofRawData = [u8; 32 * 24];
+/// The BN254 proof without the batched inverse as fixed size bytes array: 23 words.
+pub type ProofRawDataWithoutInverse = [u8; 32 * 23];
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
     derive(::serde::Serialize, ::serde::Deserialize),
-    serde(rename_all = "SCREAMING_SNAKE_CASE")
+    serde(rename_all = "SCREAMING_SNAKE_CASE", bound = "")
 )]
 /// Proof's Polynomial.
-pub struct Polynomials {
+pub struct Polynomials<C: Curve = Bn254> {
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::g1"))]
-    pub c1: G1,
+    pub c1: C::G1,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::g1"))]
-    pub c2: G1,
+    pub c2: C::G1,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::g1"))]
-    pub w1: G1,
+    pub w1: C::G1,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::g1"))]
-    pub w2: G1,
+    pub w2: C::G1,
 }
 
-#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(bound = "")
+)]
 /// Proof's Evaluation values.
-pub struct Evaluations {
+pub struct Evaluations<C: Curve = Bn254> {
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub ql: Fr,
+    pub ql: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub qr: Fr,
+    pub qr: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub qm: Fr,
+    pub qm: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub qo: Fr,
+    pub qo: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub qc: Fr,
+    pub qc: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub s1: Fr,
+    pub s1: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub s2: Fr,
+    pub s2: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub s3: Fr,
+    pub s3: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub a: Fr,
+    pub a: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub b: Fr,
+    pub b: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub c: Fr,
+    pub c: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub z: Fr,
+    pub z: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub zw: Fr,
+    pub zw: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub t1w: Fr,
+    pub t1w: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub t2w: Fr,
+    pub t2w: C::Fr,
     #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
-    pub inv: Fr,
+    pub inv: C::Fr,
 }
 
 /// The Proof data: use the implemented conversion traits `TryFrom` to build it.
-#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
-pub struct Proof {
-    pub polynomials: Polynomials,
-    pub evaluations: Evaluations,
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(bound = "", from = "crate::serde::ProofSerde<C>")
+)]
+pub struct Proof<C: Curve = Bn254> {
+    pub polynomials: Polynomials<C>,
+    pub evaluations: Evaluations<C>,
 }
 
+/// A proof without the batched inverse `inv`: the 23 words layout of the fflonk provers that
+/// leave it to the verifier. Verify it by [`crate::verify_without_inverse`], that computes
+/// the inverse instead of checking the provided one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofWithoutInverse<C: Curve = Bn254>(Proof<C>);
+
 #[derive(Snafu, Debug)]
 pub enum ProofDataError {
     #[snafu(display("Invalid field proof data '{field}': {error:?}"))]
@@ -96,6 +120,8 @@ pub enum ProofDataError {
         field: &'static str,
         error: GroupError,
     },
+    #[snafu(display("Invalid proof length {len}: expected {expected}"))]
+    WrongLength { len: usize, expected: usize },
 }
 
 #[derive(Debug)]
@@ -122,74 +148,170 @@ pub enum ProofFields {
     Inv,
 }
 
-impl TryFrom<&ProofData> for Polynomials {
-    type Error = ProofDataError;
+impl<C: Curve> Proof<C> {
+    /// The size in bytes of the encoded proof: the 4 commitments as affine points followed
+    /// by the 16 evaluations as 32-byte big-endian scalars, like the BN254 [`ProofRawData`].
+    pub const ENCODED_LEN: usize = 4 * <C::G1 as Point>::ENCODED_LEN + 16 * 32;
+
+    /// Encode the proof in [`Proof::ENCODED_LEN`] bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let g1_len = <C::G1 as Point>::ENCODED_LEN;
+        let mut out = alloc::vec![0_u8; Self::ENCODED_LEN];
+        let (points, scalars) = out.split_at_mut(4 * g1_len);
+        let polynomials = &self.polynomials;
+        for (chunk, point) in points.chunks_exact_mut(g1_len).zip([
+            polynomials.c1,
+            polynomials.c2,
+            polynomials.w1,
+            polynomials.w2,
+        ]) {
+            point.encode(chunk);
+        }
+        for (chunk, scalar) in scalars
+            .chunks_exact_mut(32)
+            .zip(self.evaluations.as_array())
+        {
+            chunk.copy_from_slice(&scalar.to_be_bytes());
+        }
+        out
+    }
 
-    fn try_from(data: &ProofData) -> Result<Self, Self::Error> {
-        use ProofFields::*;
+    /// Decode a proof from its [`Proof::ENCODED_LEN`] bytes.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProofDataError> {
+        if data.len() != Self::ENCODED_LEN {
+            return Err(ProofDataError::WrongLength {
+                len: data.len(),
+                expected: Self::ENCODED_LEN,
+            });
+        }
+        let g1_len = <C::G1 as Point>::ENCODED_LEN;
+        let (points, scalars) = data.split_at(4 * g1_len);
+        let point = |field: ProofFields, pos: usize| {
+            read_g1::<C>(field, &points[pos * g1_len..(pos + 1) * g1_len])
+        };
+        let scalar = |field: ProofFields, pos: usize| {
+            read_fr::<C>(
+                field,
+                scalars[pos * 32..(pos + 1) * 32]
+                    .try_into()
+                    .expect("Cannot fail: is a 32-byte slice"),
+            )
+        };
         Ok(Self {
-            c1: read_g1(C1, &data.0[..2])?,
-            c2: read_g1(C2, &data.0[2..4])?,
-            w1: read_g1(W1, &data.0[4..6])?,
-            w2: read_g1(W2, &data.0[6..8])?,
+            polynomials: Polynomials {
+                c1: point(ProofFields::C1, 0)?,
+                c2: point(ProofFields::C2, 1)?,
+                w1: point(ProofFields::W1, 2)?,
+                w2: point(ProofFields::W2, 3)?,
+            },
+            evaluations: Evaluations {
+                ql: scalar(ProofFields::Ql, 0)?,
+                qr: scalar(ProofFields::Qr, 1)?,
+                qm: scalar(ProofFields::Qm, 2)?,
+                qo: scalar(ProofFields::Qo, 3)?,
+                qc: scalar(ProofFields::Qc, 4)?,
+                s1: scalar(ProofFields::S1, 5)?,
+                s2: scalar(ProofFields::S2, 6)?,
+                s3: scalar(ProofFields::S3, 7)?,
+                a: scalar(ProofFields::A, 8)?,
+                b: scalar(ProofFields::B, 9)?,
+                c: scalar(ProofFields::C, 10)?,
+                z: scalar(ProofFields::Z, 11)?,
+                zw: scalar(ProofFields::Zw, 12)?,
+                t1w: scalar(ProofFields::T1w, 13)?,
+                t2w: scalar(ProofFields::T2w, 14)?,
+                inv: scalar(ProofFields::Inv, 15)?,
+            },
         })
     }
 }
 
-impl TryFrom<&ProofData> for Evaluations {
+impl<C: Curve> ProofWithoutInverse<C> {
+    /// The size in bytes of the encoded proof: [`Proof::ENCODED_LEN`] without the `inv`
+    /// 32 bytes.
+    pub const ENCODED_LEN: usize = Proof::<C>::ENCODED_LEN - 32;
+
+    /// Encode the proof in [`ProofWithoutInverse::ENCODED_LEN`] bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.0.to_bytes();
+        out.truncate(Self::ENCODED_LEN);
+        out
+    }
+
+    /// Decode a proof from its [`ProofWithoutInverse::ENCODED_LEN`] bytes or from the
+    /// [`Proof::ENCODED_LEN`] bytes of a proof with the inverse: the provided inverse is
+    /// checked to be a scalar and then dropped.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProofDataError> {
+        if data.len() == Proof::<C>::ENCODED_LEN {
+            return Proof::from_bytes(data).map(Into::into);
+        }
+        if data.len() != Self::ENCODED_LEN {
+            return Err(ProofDataError::WrongLength {
+                len: data.len(),
+                expected: Self::ENCODED_LEN,
+            });
+        }
+        let mut full = Vec::with_capacity(Proof::<C>::ENCODED_LEN);
+        full.extend_from_slice(data);
+        full.resize(Proof::<C>::ENCODED_LEN, 0);
+        Proof::from_bytes(&full).map(Self)
+    }
+
+    /// The proof with a zero `inv`: it's never a valid inverse.
+    pub(crate) fn as_proof(&self) -> &Proof<C> {
+        &self.0
+    }
+}
+
+impl<C: Curve> From<Proof<C>> for ProofWithoutInverse<C> {
+    fn from(mut proof: Proof<C>) -> Self {
+        proof.evaluations.inv = C::Fr::zero();
+        Self(proof)
+    }
+}
+
+impl From<ProofWithoutInverse> for ProofRawDataWithoutInverse {
+    fn from(value: ProofWithoutInverse) -> Self {
+        value
+            .to_bytes()
+            .try_into()
+            .expect("Cannot fail: a BN254 proof without inverse is 736 bytes")
+    }
+}
+
+impl TryFrom<&ProofRawDataWithoutInverse> for ProofWithoutInverse {
     type Error = ProofDataError;
 
-    fn try_from(data: &ProofData) -> Result<Self, Self::Error> {
-        use ProofFields::*;
-        Ok(Self {
-            ql: read_fr(Ql, data.0[8])?,
-            qr: read_fr(Qr, data.0[9])?,
-            qm: read_fr(Qm, data.0[10])?,
-            qo: read_fr(Qo, data.0[11])?,
-            qc: read_fr(Qc, data.0[12])?,
-            s1: read_fr(S1, data.0[13])?,
-            s2: read_fr(S2, data.0[14])?,
-            s3: read_fr(S3, data.0[15])?,
-            a: read_fr(A, data.0[16])?,
-            b: read_fr(B, data.0[17])?,
-            c: read_fr(C, data.0[18])?,
-            z: read_fr(Z, data.0[19])?,
-            zw: read_fr(Zw, data.0[20])?,
-            t1w: read_fr(T1w, data.0[21])?,
-            t2w: read_fr(T2w, data.0[22])?,
-            inv: read_fr(Inv, data.0[23])?,
-        })
+    fn try_from(data: &ProofRawDataWithoutInverse) -> Result<Self, Self::Error> {
+        ProofWithoutInverse::from_bytes(data)
+    }
+}
+
+impl TryFrom<&ProofRawData> for ProofWithoutInverse {
+    type Error = ProofDataError;
+
+    fn try_from(data: &ProofRawData) -> Result<Self, Self::Error> {
+        ProofWithoutInverse::from_bytes(data)
+    }
+}
+
+impl<C: Curve> Evaluations<C> {
+    fn as_array(&self) -> [C::Fr; 16] {
+        [
+            self.ql, self.qr, self.qm, self.qo, self.qc, self.s1, self.s2, self.s3, self.a, self.b,
+            self.c, self.z, self.zw, self.t1w, self.t2w, self.inv,
+        ]
     }
 }
 
 impl From<Proof> for ProofData {
     fn from(value: Proof) -> Self {
-        Self([
-            value.polynomials.c1.x().into_u256(),
-            value.polynomials.c1.y().into_u256(),
-            value.polynomials.c2.x().into_u256(),
-            value.polynomials.c2.y().into_u256(),
-            value.polynomials.w1.x().into_u256(),
-            value.polynomials.w1.y().into_u256(),
-            value.polynomials.w2.x().into_u256(),
-            value.polynomials.w2.y().into_u256(),
-            value.evaluations.ql.into_u256(),
-            value.evaluations.qr.into_u256(),
-            value.evaluations.qm.into_u256(),
-            value.evaluations.qo.into_u256(),
-            value.evaluations.qc.into_u256(),
-            value.evaluations.s1.into_u256(),
-            value.evaluations.s2.into_u256(),
-            value.evaluations.s3.into_u256(),
-            value.evaluations.a.into_u256(),
-            value.evaluations.b.into_u256(),
-            value.evaluations.c.into_u256(),
-            value.evaluations.z.into_u256(),
-            value.evaluations.zw.into_u256(),
-            value.evaluations.t1w.into_u256(),
-            value.evaluations.t2w.into_u256(),
-            value.evaluations.inv.into_u256(),
-        ])
+        let raw = ProofRawData::from(value);
+        let mut words = [U256::zero(); 24];
+        for (word, chunk) in words.iter_mut().zip(raw.chunks_exact(32)) {
+            *word = U256::from_slice(chunk).expect("Cannot fail to read 32 bytes");
+        }
+        Self(words)
     }
 }
 
@@ -214,7 +336,10 @@ impl From<ProofData> for ProofRawData {
 
 impl From<Proof> for ProofRawData {
     fn from(value: Proof) -> Self {
-        ProofData::from(value).into()
+        value
+            .to_bytes()
+            .try_into()
+            .expect("Cannot fail: a BN254 proof is 768 bytes")
     }
 }
 
@@ -222,10 +347,7 @@ impl TryFrom<&ProofData> for Proof {
     type Error = ProofDataError;
 
     fn try_from(data: &ProofData) -> Result<Self, Self::Error> {
-        Ok(Self {
-            polynomials: Polynomials::try_from(data)?,
-            evaluations: Evaluations::try_from(data)?,
-        })
+        (&ProofRawData::from(data.clone())).try_into()
     }
 }
 
@@ -241,38 +363,12 @@ impl TryFrom<&ProofRawData> for Proof {
     type Error = ProofDataError;
 
     fn try_from(data: &ProofRawData) -> Result<Self, Self::Error> {
-        ProofData([
-            U256::from_slice(&data[0x000..0x020]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x020..0x040]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x040..0x060]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x060..0x080]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x080..0x0A0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x0A0..0x0C0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x0C0..0x0E0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x0E0..0x100]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x100..0x120]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x120..0x140]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x140..0x160]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x160..0x180]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x180..0x1A0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x1A0..0x1C0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x1C0..0x1E0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x1E0..0x200]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x200..0x220]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x220..0x240]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x240..0x260]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x260..0x280]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x280..0x2A0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x2A0..0x2C0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x2C0..0x2E0]).expect("Cannot fail to read 32 bytes"),
-            U256::from_slice(&data[0x2E0..0x300]).expect("Cannot fail to read 32 bytes"),
-        ])
-        .try_into()
+        Proof::from_bytes(data)
     }
 }
 
 impl ProofFields {
-    fn str(&self) -> &'static str {
+    pub(crate) fn str(&self) -> &'static str {
         match self {
             ProofFields::C1 => "c1",
             ProofFields::C2 => "c2",
@@ -318,26 +414,28 @@ impl ProofFields {
     }
 }
 
-fn read_g1(field: ProofFields, data: &[U256]) -> Result<G1, ProofDataError> {
-    let x = read_fq(field.x_str(), data[0])?;
-    let y = read_fq(field.y_str(), data[1])?;
-    AffineG1::new(x, y)
-        .map_err(|e| ProofDataError::InvalidGroup {
+fn read_g1<C: Curve>(field: ProofFields, data: &[u8]) -> Result<C::G1, ProofDataError> {
+    C::G1::decode(data).map_err(|e| match e {
+        PointError::InvalidLength { len, expected } => {
+            ProofDataError::WrongLength { len, expected }
+        }
+        PointError::InvalidCoordinate { index: 0 } => ProofDataError::InvalidField {
+            field: field.x_str(),
+            error: FieldError::NotMember,
+        },
+        PointError::InvalidCoordinate { .. } => ProofDataError::InvalidField {
+            field: field.y_str(),
+            error: FieldError::NotMember,
+        },
+        PointError::InvalidPoint { error } => ProofDataError::InvalidGroup {
             field: field.str(),
-            error: e,
-        })
-        .map(Into::into)
-}
-
-fn read_fq(addr: &'static str, data: U256) -> Result<Fq, ProofDataError> {
-    Fq::from_u256(data).map_err(|e| ProofDataError::InvalidField {
-        field: addr,
-        error: e,
+            error,
+        },
     })
 }
 
-fn read_fr(field: ProofFields, data: U256) -> Result<Fr, ProofDataError> {
-    Fr::new(data).ok_or_else(|| ProofDataError::InvalidField {
+fn read_fr<C: Curve>(field: ProofFields, data: &[u8; 32]) -> Result<C::Fr, ProofDataError> {
+    C::Fr::from_be_bytes(data).ok_or_else(|| ProofDataError::InvalidField {
         field: field.str(),
         error: FieldError::NotMember,
     })
@@ -444,6 +542,47 @@ mod should {
         Proof::try_from(&proof_data).unwrap();
     }
 
+    #[test]
+    fn accept_just_the_canonical_bytes() {
+        for pos in 0..PROOF_RAW_DATA.len() {
+            for value in [0x00, 0xff, PROOF_RAW_DATA[pos] ^ 0x01] {
+                let mut data = PROOF_RAW_DATA;
+                data[pos] = value;
+
+                if let Ok(proof) = <Proof>::from_bytes(&data) {
+                    assert_eq!(data.as_slice(), proof.to_bytes(), "Byte {pos} = {value:#x}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn read_a_proof_without_inverse_from_both_layouts() {
+        let data: &ProofRawDataWithoutInverse = PROOF_RAW_DATA[..32 * 23].try_into().unwrap();
+
+        let proof = ProofWithoutInverse::try_from(data).unwrap();
+
+        assert_eq!(
+            proof,
+            ProofWithoutInverse::try_from(&PROOF_RAW_DATA).unwrap()
+        );
+        assert_eq!(proof, Proof::try_from(&PROOF_RAW_DATA).unwrap().into());
+        assert_eq!(*data, ProofRawDataWithoutInverse::from(proof));
+    }
+
+    #[rstest]
+    #[case::too_short(32 * 22)]
+    #[case::between(32 * 23 + 1)]
+    #[case::too_long(32 * 25)]
+    fn reject_a_proof_without_inverse_of_wrong_length(#[case] len: usize) {
+        let data = alloc::vec![1; len];
+
+        assert!(matches!(
+            <ProofWithoutInverse>::from_bytes(&data),
+            Err(ProofDataError::WrongLength { len: l, expected: 736 }) if l == len
+        ));
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn deserialize_a_valid_snarkjs_proof() {
@@ -0,0 +1,297 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "scale")]
+
+//! The SCALE codec of the proofs, the verification keys and the public inputs. The proofs and
+//! the keys are encoded as their fixed size bytes (`Proof::to_bytes()` and
+//! `VerificationKey::to_bytes()`) and decoded with all the field and curve checks, like
+//! `Proof::from_bytes()` and `VerificationKey::from_bytes()` do. The public inputs are a
+//! sequence of 32 bytes big-endian words and the keys identifiers are their 32 hash bytes.
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, EncodeLike, Error, Input, MaxEncodedLen, Output};
+use scale_info::{build::Fields, meta_type, Path, Type, TypeDefArray, TypeInfo};
+use substrate_bn::arith::U256;
+
+use crate::{curve::Curve, utils::IntoBytes as _, Proof, Public, VerificationKey, VkId};
+
+/// Read the `len` bytes of a fixed size encoding.
+fn read_bytes<I: Input>(input: &mut I, len: usize) -> Result<Vec<u8>, Error> {
+    let mut data = alloc::vec![0; len];
+    input.read(&mut data)?;
+    Ok(data)
+}
+
+/// A fixed size bytes encoding: the type is an array of `len` bytes.
+fn bytes_type(name: &'static str, len: usize, docs: &[&'static str]) -> Type {
+    Type::new(
+        Path::new(name, "fflonk_verifier"),
+        Vec::new(),
+        TypeDefArray::new(len as u32, meta_type::<u8>()),
+        docs.to_vec(),
+    )
+}
+
+impl<C: Curve> Encode for Proof<C> {
+    fn size_hint(&self) -> usize {
+        Self::ENCODED_LEN
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_bytes())
+    }
+}
+
+impl<C: Curve> EncodeLike for Proof<C> {}
+
+impl<C: Curve> Decode for Proof<C> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        Self::from_bytes(&read_bytes(input, Self::ENCODED_LEN)?)
+            .map_err(|_| "Invalid fflonk proof data".into())
+    }
+}
+
+impl<C: Curve> MaxEncodedLen for Proof<C> {
+    fn max_encoded_len() -> usize {
+        Self::ENCODED_LEN
+    }
+}
+
+impl<C: Curve> TypeInfo for Proof<C> {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        bytes_type("Proof", Self::ENCODED_LEN, &["The fflonk proof bytes"])
+    }
+}
+
+impl<C: Curve> Encode for VerificationKey<C> {
+    fn size_hint(&self) -> usize {
+        Self::ENCODED_LEN
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_bytes())
+    }
+}
+
+impl<C: Curve> EncodeLike for VerificationKey<C> {}
+
+impl<C: Curve> Decode for VerificationKey<C> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        Self::from_bytes(&read_bytes(input, Self::ENCODED_LEN)?)
+            .map_err(|_| "Invalid fflonk verification key data".into())
+    }
+}
+
+impl<C: Curve> MaxEncodedLen for VerificationKey<C> {
+    fn max_encoded_len() -> usize {
+        Self::ENCODED_LEN
+    }
+}
+
+impl<C: Curve> TypeInfo for VerificationKey<C> {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        bytes_type(
+            "VerificationKey",
+            Self::ENCODED_LEN,
+            &["The fflonk verification key bytes"],
+        )
+    }
+}
+
+impl Encode for VkId {
+    fn size_hint(&self) -> usize {
+        32
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.0)
+    }
+}
+
+impl EncodeLike for VkId {}
+
+impl Decode for VkId {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        <[u8; 32]>::decode(input).map(Self)
+    }
+}
+
+impl MaxEncodedLen for VkId {
+    fn max_encoded_len() -> usize {
+        32
+    }
+}
+
+impl TypeInfo for VkId {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        bytes_type("VkId", 32, &["The fflonk verification key identifier"])
+    }
+}
+
+/// The public inputs are unbounded, so they don't implement `MaxEncodedLen`: bound them
+/// in the runtime storage (e.g. by a `BoundedVec<[u8; 32], _>`).
+impl Encode for Public {
+    fn size_hint(&self) -> usize {
+        self.words().size_hint()
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.words().encode_to(dest)
+    }
+}
+
+impl EncodeLike for Public {}
+
+impl Decode for Public {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        Ok(Vec::<[u8; 32]>::decode(input)?
+            .iter()
+            .map(|word| U256::from_slice(word).expect("Cannot fail: is a 32-byte word"))
+            .collect())
+    }
+}
+
+impl TypeInfo for Public {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("Public", "fflonk_verifier"))
+            .docs(&["The fflonk public inputs as 32 bytes big-endian words"])
+            .composite(Fields::unnamed().field(|f| f.ty::<Vec<[u8; 32]>>()))
+    }
+}
+
+impl Public {
+    fn words(&self) -> Vec<[u8; 32]> {
+        self.0.iter().map(|value| value.into_bytes()).collect()
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use codec::DecodeAll;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::macros::u256;
+
+    fn proof() -> Proof {
+        let data = hex::decode(&include_str!("../resources/bins/proof.hex").trim()[2..]).unwrap();
+        Proof::from_bytes(&data).unwrap()
+    }
+
+    #[test]
+    fn encode_the_proof_as_its_bytes() {
+        let proof = proof();
+
+        let encoded = proof.encode();
+
+        assert_eq!(proof.to_bytes(), encoded);
+        assert_eq!(<Proof>::max_encoded_len(), encoded.len());
+        assert_eq!(proof, Proof::decode_all(&mut encoded.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn encode_the_verification_key_as_its_bytes() {
+        let vk = VerificationKey::default();
+
+        let encoded = vk.encode();
+
+        assert_eq!(vk.to_bytes(), encoded);
+        assert_eq!(<VerificationKey>::max_encoded_len(), encoded.len());
+        assert_eq!(
+            vk,
+            VerificationKey::decode_all(&mut encoded.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_the_key_id_as_its_bytes() {
+        let id = VerificationKey::default().hash();
+
+        let encoded = id.encode();
+
+        assert_eq!(id.as_bytes().as_slice(), encoded);
+        assert_eq!(VkId::max_encoded_len(), encoded.len());
+        assert_eq!(id, VkId::decode_all(&mut encoded.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn encode_the_public_inputs_as_a_words_sequence() {
+        let pubs = Public::from(
+            [
+                u256!("110d778eaf8b8ef7ac10f8ac239a14df0eb292a8d1b71340d527b26301a9ab08"),
+                U256::from(7),
+            ]
+            .as_slice(),
+        );
+
+        let encoded = pubs.encode();
+
+        assert_eq!(8, encoded[0]);
+        assert_eq!(1 + 64, encoded.len());
+        assert_eq!(pubs, Public::decode_all(&mut encoded.as_slice()).unwrap());
+    }
+
+    #[rstest]
+    #[case::invalid_field(32 * 23, 0xff)]
+    #[case::invalid_point(0, 0x01)]
+    fn reject_invalid_proofs(#[case] offset: usize, #[case] value: u8) {
+        let mut encoded = proof().encode();
+        encoded[offset..offset + 32].fill(value);
+
+        assert!(<Proof>::decode(&mut encoded.as_slice()).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_verification_keys() {
+        let mut encoded = VerificationKey::default().encode();
+        let c0 = encoded.len() - 64;
+        encoded[c0..].fill(0x01);
+
+        assert!(<VerificationKey>::decode(&mut encoded.as_slice()).is_err());
+    }
+
+    #[test]
+    fn reject_truncated_data() {
+        let encoded = proof().encode();
+
+        assert!(<Proof>::decode(&mut &encoded[1..]).is_err());
+    }
+
+    #[test]
+    fn describe_the_types_as_fixed_size_bytes() {
+        let mut registry = scale_info::Registry::new();
+        let proof = registry.register_type(&meta_type::<Proof>());
+        let registry = scale_info::PortableRegistry::from(registry);
+
+        let ty = registry.resolve(proof.id).unwrap();
+
+        assert_eq!(["fflonk_verifier", "Proof"], ty.path.segments.as_slice());
+        assert!(matches!(
+            &ty.type_def,
+            scale_info::TypeDef::Array(array) if array.len == 768
+        ));
+    }
+}
@@ -16,170 +16,694 @@
 
 #![cfg(feature = "serde")]
 
-pub mod fr {
-    use substrate_bn::{arith::U256, Fr};
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+
+use crate::{
+    curve::{Curve, Point},
+    key::PROTOCOL,
+    proof::{Evaluations, Polynomials},
+    Proof, VerificationKey, VerificationKeyError,
+};
+
+/// The way the serde formats values are decoded: [`Lenient`] or [`Canonical`].
+pub(crate) trait Decoding {
+    /// Reject the values that are not encoded as their serialization does.
+    const CANONICAL: bool;
+}
+
+/// Accept the decimal strings with leading zeros, reduce the scalars modulo the field
+/// order and ignore the coordinates of the points at infinity.
+pub(crate) enum Lenient {}
 
-    use crate::utils::{IntoBytes, IntoFr};
+/// Accept just the encodings that are byte-identical to their re-encoding.
+pub(crate) enum Canonical {}
 
-    pub fn serialize<S>(fr: &Fr, s: S) -> Result<S::Ok, S::Error>
+impl Decoding for Lenient {
+    const CANONICAL: bool = false;
+}
+
+impl Decoding for Canonical {
+    const CANONICAL: bool = true;
+}
+
+/// Decode a value with the strict canonical decoding: the decimal strings should not have
+/// leading zeros (and the bytes strings should have the serialized length), the scalars
+/// should be less than the field order and the points at infinity should be the snarkjs'
+/// `(0, 1, 0)`. Use it to read the proofs and the keys when their encodings are hashed
+/// (e.g. for replay protection), so they cannot be malleated.
+///
+/// It's implemented for [`Proof`] and [`VerificationKey`] and serializes as the wrapped
+/// value. The binary encodings (`Proof::from_bytes`, `VerificationKey::from_bytes`, the
+/// calldata and the SCALE codec) are always canonical.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Strict<T>(pub T);
+
+impl<T: serde::Serialize> serde::Serialize for Strict<T> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let u256 = fr.into_u256();
-        if s.is_human_readable() {
-            ethnum::serde::decimal::serialize(&ethnum::U256::from_words(u256.0[1], u256.0[0]), s)
-        } else {
-            s.serialize_bytes(&u256.into_bytes())
-        }
+        self.0.serialize(s)
     }
+}
 
-    pub fn deserialize<'de, D>(data: D) -> Result<Fr, D::Error>
+impl<'de, C: Curve> serde::Deserialize<'de> for Strict<Proof<C>> {
+    fn deserialize<D>(data: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        if data.is_human_readable() {
-            let u256: ethnum::U256 = ethnum::serde::decimal::deserialize(data)?;
-            Ok(U256([u256.0[0], u256.0[1]]).into_fr())
-        } else {
-            <[u8; 32] as serde::Deserialize>::deserialize(data).map(IntoFr::into_fr)
-        }
+        ProofSerde::<C, Canonical>::deserialize(data).map(|proof| Self(proof.into()))
     }
 }
 
-mod fq {
-    use substrate_bn::{arith::U256, Fq};
+impl<'de, C: Curve> serde::Deserialize<'de> for Strict<VerificationKey<C>> {
+    fn deserialize<D>(data: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        VerificationKeySerde::<C, Canonical>::deserialize(data)?
+            .try_into()
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
 
-    use crate::utils::{IntoBytes, IntoU256};
+/// An unsigned big integer as big-endian bytes: a decimal string in the human readable
+/// formats (like snarkjs) and a bytes string in the other ones.
+struct BigUint {
+    bytes: Vec<u8>,
+    /// If read from a decimal string: whether it had leading zeros.
+    padded: Option<bool>,
+}
+
+impl BigUint {
+    fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            padded: None,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        Self::new(alloc::vec![value])
+    }
+
+    /// The value as `len` big-endian bytes: `None` if it doesn't fit.
+    fn to_be_bytes(&self, len: usize) -> Option<Vec<u8>> {
+        let skip = self.bytes.iter().take_while(|b| **b == 0).count();
+        let value = &self.bytes[skip..];
+        (value.len() <= len).then(|| {
+            let mut out = alloc::vec![0_u8; len];
+            out[len - value.len()..].copy_from_slice(value);
+            out
+        })
+    }
+
+    /// Like [`BigUint::to_be_bytes`] but, with the `M` canonical decoding, `None` also if it
+    /// isn't encoded as its serialization: a decimal string without leading zeros or
+    /// exactly `len` bytes.
+    fn to_be_bytes_as<M: Decoding>(&self, len: usize) -> Option<Vec<u8>> {
+        let canonical = match self.padded {
+            Some(padded) => !padded,
+            None => self.bytes.len() == len,
+        };
+        (canonical || !M::CANONICAL)
+            .then(|| self.to_be_bytes(len))
+            .flatten()
+    }
 
-    pub fn serialize<S>(fq: &Fq, s: S) -> Result<S::Ok, S::Error>
+    /// Whether it's `value`: with the `M` canonical decoding, the value should also be
+    /// encoded as its serialization, a decimal string without leading zeros or a byte.
+    fn is<M: Decoding>(&self, value: u8) -> bool {
+        self.to_be_bytes_as::<M>(1) == Some(alloc::vec![value])
+    }
+
+    fn to_decimal(&self) -> String {
+        let mut value = self.bytes.clone();
+        let mut digits = Vec::new();
+        loop {
+            let mut remainder = 0_u32;
+            for byte in value.iter_mut() {
+                let current = (remainder << 8) | *byte as u32;
+                *byte = (current / 10) as u8;
+                remainder = current % 10;
+            }
+            digits.push(b'0' + remainder as u8);
+            if value.iter().all(|b| *b == 0) {
+                break;
+            }
+        }
+        digits.iter().rev().map(|d| *d as char).collect()
+    }
+
+    /// The digits of the largest coordinate, a 32 bytes base field element: longer strings
+    /// are rejected before the conversion, whose time is quadratic in the length.
+    const MAX_DECIMAL_LEN: usize = 78;
+
+    fn from_decimal(decimal: &str) -> Option<Self> {
+        if decimal.is_empty() || decimal.len() > Self::MAX_DECIMAL_LEN {
+            return None;
+        }
+        let mut value = Vec::new();
+        for digit in decimal.bytes() {
+            let mut carry = (digit as char).to_digit(10)?;
+            for byte in value.iter_mut().rev() {
+                let current = *byte as u32 * 10 + carry;
+                *byte = current as u8;
+                carry = current >> 8;
+            }
+            if carry > 0 {
+                value.insert(0, carry as u8);
+            }
+        }
+        Some(Self {
+            bytes: value,
+            padded: Some(decimal.len() > 1 && decimal.starts_with('0')),
+        })
+    }
+}
+
+impl serde::Serialize for BigUint {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let u256 = fq.into_u256();
         if s.is_human_readable() {
-            ethnum::serde::decimal::serialize(&ethnum::U256::from_words(u256.0[1], u256.0[0]), s)
+            s.serialize_str(&self.to_decimal())
         } else {
-            s.serialize_bytes(&u256.into_bytes())
+            s.serialize_bytes(&self.bytes)
         }
     }
+}
 
-    pub fn deserialize<'de, D>(data: D) -> Result<Fq, D::Error>
+impl<'de> serde::Deserialize<'de> for BigUint {
+    fn deserialize<D>(data: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let u256 = if data.is_human_readable() {
-            let u256: ethnum::U256 = ethnum::serde::decimal::deserialize(data)?;
-            U256([u256.0[0], u256.0[1]])
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = BigUint;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("an unsigned integer as decimal string or big-endian bytes")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                BigUint::from_decimal(v).ok_or_else(|| E::custom("Invalid decimal value"))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(BigUint::new(v.to_vec()))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut value = Vec::new();
+                while let Some(byte) = seq.next_element()? {
+                    value.push(byte);
+                }
+                Ok(BigUint::new(value))
+            }
+        }
+
+        if data.is_human_readable() {
+            data.deserialize_str(Visitor)
         } else {
-            <[u8; 32] as serde::Deserialize>::deserialize(data).map(IntoU256::into_u256)?
-        };
-        Fq::from_u256(u256).map_err(|_e| serde::de::Error::custom("Invalid Fq value"))
+            data.deserialize_bytes(Visitor)
+        }
     }
 }
 
-mod fq2 {
+pub mod fr {
     use serde::{Deserialize, Serialize};
-    use substrate_bn::{Fq, Fq2};
 
-    #[derive(Serialize, Deserialize)]
-    struct Fq2Serde(
-        #[serde(with = "super::fq")] Fq,
-        #[serde(with = "super::fq")] Fq,
-    );
+    use super::{BigUint, Decoding, Lenient};
+    use crate::curve::Field;
 
-    pub fn serialize<S>(fq2: &Fq2, s: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<F: Field, S>(fr: &F, s: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        Fq2Serde(fq2.real(), fq2.imaginary()).serialize(s)
+        BigUint::new(fr.to_be_bytes().to_vec()).serialize(s)
+    }
+
+    pub fn deserialize<'de, F: Field, D>(data: D) -> Result<F, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as::<Lenient, F, D>(data)
     }
 
-    pub fn deserialize<'de, D>(data: D) -> Result<Fq2, D::Error>
+    /// Deserialize a scalar with the `M` decoding: the canonical one rejects the values
+    /// that are not less than the field order instead of reducing them.
+    pub(crate) fn deserialize_as<'de, M: Decoding, F: Field, D>(data: D) -> Result<F, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let fq2 = Fq2Serde::deserialize(data)?;
-        Ok(Fq2::new(fq2.0, fq2.1))
+        let invalid = || serde::de::Error::custom("Invalid Fr value");
+        let bytes: [u8; 32] = BigUint::deserialize(data)?
+            .to_be_bytes_as::<M>(32)
+            .ok_or_else(invalid)?
+            .try_into()
+            .expect("Cannot fail: is a 32-byte vector");
+        if M::CANONICAL {
+            F::from_be_bytes(&bytes).ok_or_else(invalid)
+        } else {
+            Ok(F::from_be_bytes_reduced(&bytes))
+        }
     }
 }
 
-pub mod g2 {
-    use serde::{Deserialize, Serialize};
-    use substrate_bn::{AffineG2, Fq2, Group, G2};
+/// The snarkjs' projective coordinates of a point: `(x, y, 1)` for the affine points and
+/// `(0, 1, 0)` for the point at infinity, with every `Fq2` element as `(real, imaginary)`.
+fn serialize_point<P: Point, S>(point: &P, s: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeTuple;
 
-    #[derive(Serialize, Deserialize)]
-    struct G2Serde(
-        #[serde(with = "super::fq2")] Fq2,
-        #[serde(with = "super::fq2")] Fq2,
-        #[serde(with = "super::fq2")] Fq2,
-    );
+    let mut encoded = alloc::vec![0_u8; P::ENCODED_LEN];
+    point.encode(&mut encoded);
+    let z = if point.is_zero() {
+        // The infinity `y` is 1: the last encoded byte is the `y` (real part) lowest one
+        encoded[P::ENCODED_LEN - 1] = 1;
+        0
+    } else {
+        1
+    };
+    let (x, y) = encoded.split_at(P::ENCODED_LEN / 2);
+    let mut tuple = s.serialize_tuple(3)?;
+    if P::COORDINATES == 2 {
+        tuple.serialize_element(&BigUint::new(x.to_vec()))?;
+        tuple.serialize_element(&BigUint::new(y.to_vec()))?;
+        tuple.serialize_element(&BigUint::from_u8(z))?;
+    } else {
+        // EIP-197 ordering: the imaginary part comes first
+        let len = P::COORDINATE_LEN;
+        for c in [x, y] {
+            tuple.serialize_element(&(
+                BigUint::new(c[len..].to_vec()),
+                BigUint::new(c[..len].to_vec()),
+            ))?;
+        }
+        tuple.serialize_element(&(BigUint::from_u8(z), BigUint::from_u8(0)))?;
+    }
+    tuple.end()
+}
 
-    pub fn serialize<S>(g2: &G2, s: S) -> Result<S::Ok, S::Error>
+/// Decode the point from its `xy` big-endian coordinates (the `Fq2` ones with the
+/// imaginary part first) and the `z` Jacobian one (imaginary part first too): with the
+/// canonical `M` decoding `z` should be 1, or 0 with the point at infinity as `(0, 1, 0)`,
+/// while the lenient one accepts any `z`.
+fn deserialize_point<'de, M: Decoding, P: Point, D>(
+    xy: [Vec<u8>; 2],
+    z: &[BigUint],
+    name: &str,
+) -> Result<P, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let invalid = || serde::de::Error::custom(alloc::format!("Invalid {name} point"));
+    let [x, y] = xy;
+    let (z_low, z_high) = z.split_last().expect("BUG: z has at least one element");
+    let z_is = |value: u8| z_high.iter().all(|c| c.is::<M>(0)) && z_low.is::<M>(value);
+    if z_is(0) {
+        let mut infinity = alloc::vec![0_u8; P::ENCODED_LEN / 2];
+        let canonical = x == infinity && {
+            infinity[P::ENCODED_LEN / 2 - 1] = 1;
+            y == infinity
+        };
+        return (canonical || !M::CANONICAL)
+            .then(P::zero)
+            .ok_or_else(invalid);
+    }
+    if z_is(1) {
+        return P::decode(&[x, y].concat()).map_err(|_e| invalid());
+    }
+    if M::CANONICAL {
+        return Err(invalid());
+    }
+    let z = z
+        .iter()
+        .map(|c| c.to_be_bytes(P::COORDINATE_LEN).ok_or_else(invalid))
+        .collect::<Result<Vec<_>, _>>()?;
+    P::decode_jacobian(&[x, y].concat(), &z.concat()).map_err(|_e| invalid())
+}
+
+pub mod g1 {
+    use serde::Deserialize;
+
+    use super::{BigUint, Decoding, Lenient};
+    use crate::curve::Point;
+
+    pub fn serialize<P: Point, S>(g1: &P, s: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        G2Serde(g2.x(), g2.y(), g2.z()).serialize(s)
+        super::serialize_point(g1, s)
     }
 
-    fn check_point<'de, D>(point: &G2) -> Result<(), D::Error>
+    pub fn deserialize<'de, P: Point, D>(data: D) -> Result<P, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let mut check = point.clone();
-        check.normalize();
-        AffineG2::new(check.x(), check.y())
-            .map_err(|_e| serde::de::Error::custom("Invalid G2 point"))?;
-        Ok(())
+        deserialize_as::<Lenient, P, D>(data)
     }
 
-    pub fn deserialize<'de, D>(data: D) -> Result<G2, D::Error>
+    /// Deserialize a point with the `M` decoding.
+    pub(crate) fn deserialize_as<'de, M: Decoding, P: Point, D>(data: D) -> Result<P, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let g2 = G2Serde::deserialize(data)?;
-        let candidate = G2::new(g2.0, g2.1, g2.2);
-        check_point::<D>(&candidate)?;
-        Ok(candidate)
+        let invalid = || serde::de::Error::custom("Invalid G1 point");
+        let len = P::ENCODED_LEN / 2;
+        let (x, y, z) = <(BigUint, BigUint, BigUint)>::deserialize(data)?;
+        let x = x.to_be_bytes_as::<M>(len).ok_or_else(invalid)?;
+        let y = y.to_be_bytes_as::<M>(len).ok_or_else(invalid)?;
+        super::deserialize_point::<M, P, D>([x, y], &[z], "G1")
     }
 }
 
-pub mod g1 {
-    use serde::{Deserialize, Serialize};
-    use substrate_bn::{AffineG1, Fq, Group, G1};
+pub mod g2 {
+    use alloc::vec::Vec;
+    use serde::Deserialize;
 
-    #[derive(Serialize, Deserialize)]
-    struct G1Serde(
-        #[serde(with = "super::fq")] Fq,
-        #[serde(with = "super::fq")] Fq,
-        #[serde(with = "super::fq")] Fq,
-    );
+    use super::{BigUint, Decoding};
+    use crate::curve::Point;
 
-    pub fn serialize<S>(g1: &G1, s: S) -> Result<S::Ok, S::Error>
+    type Fq2Serde = (BigUint, BigUint);
+
+    pub fn serialize<P: Point, S>(g2: &P, s: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        G1Serde(g1.x(), g1.y(), g1.z()).serialize(s)
+        super::serialize_point(g2, s)
     }
 
-    fn check_point<'de, D>(point: &G1) -> Result<(), D::Error>
+    /// Deserialize a point with the `M` decoding.
+    pub(crate) fn deserialize_as<'de, M: Decoding, P: Point, D>(data: D) -> Result<P, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let mut check = point.clone();
-        check.normalize();
-        AffineG1::new(check.x(), check.y())
-            .map_err(|_e| serde::de::Error::custom("Invalid G1 point"))?;
-        Ok(())
+        let len = P::COORDINATE_LEN;
+        let invalid = || serde::de::Error::custom("Invalid G2 point");
+        // EIP-197 ordering: the imaginary part comes first
+        let join = |(real, imaginary): Fq2Serde| -> Result<Vec<u8>, D::Error> {
+            let mut bytes: Vec<u8> = imaginary.to_be_bytes_as::<M>(len).ok_or_else(invalid)?;
+            bytes.extend(real.to_be_bytes_as::<M>(len).ok_or_else(invalid)?);
+            Ok(bytes)
+        };
+        let (x, y, (z_real, z_imaginary)) = <(Fq2Serde, Fq2Serde, Fq2Serde)>::deserialize(data)?;
+        super::deserialize_point::<M, P, D>([join(x)?, join(y)?], &[z_imaginary, z_real], "G2")
     }
+}
 
-    pub fn deserialize<'de, D>(data: D) -> Result<G1, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let g1 = G1Serde::deserialize(data)?;
-        let candidate = G1::new(g1.0, g1.1, g1.2);
-        check_point::<D>(&candidate)?;
-        Ok(candidate)
+/// The snarkjs' verification key layout: the key data come with the protocol, the curve
+/// and the number of public inputs that are checked when deserialized with the `M` decoding.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct VerificationKeySerde<C: Curve, M: Decoding = Lenient> {
+    protocol: String,
+    curve: String,
+    #[serde(rename = "nPublic")]
+    n_public: usize,
+    power: u8,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    k1: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    k2: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    w: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    w3: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    w4: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    w8: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    wr: C::Fr,
+    #[serde(
+        rename = "X_2",
+        serialize_with = "g2::serialize",
+        deserialize_with = "g2::deserialize_as::<M, _, _>"
+    )]
+    x2: C::G2,
+    #[serde(
+        rename = "C0",
+        serialize_with = "g1::serialize",
+        deserialize_with = "g1::deserialize_as::<M, _, _>"
+    )]
+    c0: C::G1,
+    #[serde(skip)]
+    curve_type: PhantomData<(C, M)>,
+}
+
+impl<C: Curve> From<VerificationKey<C>> for VerificationKeySerde<C> {
+    fn from(vk: VerificationKey<C>) -> Self {
+        Self {
+            protocol: PROTOCOL.into(),
+            curve: C::NAME.into(),
+            n_public: vk.n_public,
+            power: vk.power,
+            k1: vk.k1,
+            k2: vk.k2,
+            w: vk.w,
+            w3: vk.w3,
+            w4: vk.w4,
+            w8: vk.w8,
+            wr: vk.wr,
+            x2: vk.x2,
+            c0: vk.c0,
+            curve_type: PhantomData,
+        }
+    }
+}
+
+impl<C: Curve, M: Decoding> TryFrom<VerificationKeySerde<C, M>> for VerificationKey<C> {
+    type Error = VerificationKeyError;
+
+    fn try_from(vk: VerificationKeySerde<C, M>) -> Result<Self, Self::Error> {
+        if vk.protocol != PROTOCOL {
+            return Err(VerificationKeyError::UnsupportedProtocol {
+                protocol: vk.protocol,
+            });
+        }
+        if vk.curve != C::NAME {
+            return Err(VerificationKeyError::UnsupportedCurve {
+                curve: vk.curve,
+                expected: C::NAME,
+            });
+        }
+        let vk = Self {
+            n_public: vk.n_public,
+            power: vk.power,
+            k1: vk.k1,
+            k2: vk.k2,
+            w: vk.w,
+            w3: vk.w3,
+            w4: vk.w4,
+            w8: vk.w8,
+            wr: vk.wr,
+            x2: vk.x2,
+            c0: vk.c0,
+        };
+        vk.validate()
+            .map_err(|source| VerificationKeyError::InvalidKey { source })?;
+        Ok(vk)
+    }
+}
+
+/// The snarkjs' proof layout deserialized with the `M` decoding.
+#[derive(::serde::Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct ProofSerde<C: Curve, M: Decoding = Lenient> {
+    polynomials: PolynomialsSerde<C, M>,
+    evaluations: EvaluationsSerde<C, M>,
+}
+
+#[derive(::serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", bound = "")]
+struct PolynomialsSerde<C: Curve, M: Decoding> {
+    #[serde(
+        serialize_with = "g1::serialize",
+        deserialize_with = "g1::deserialize_as::<M, _, _>"
+    )]
+    c1: C::G1,
+    #[serde(
+        serialize_with = "g1::serialize",
+        deserialize_with = "g1::deserialize_as::<M, _, _>"
+    )]
+    c2: C::G1,
+    #[serde(
+        serialize_with = "g1::serialize",
+        deserialize_with = "g1::deserialize_as::<M, _, _>"
+    )]
+    w1: C::G1,
+    #[serde(
+        serialize_with = "g1::serialize",
+        deserialize_with = "g1::deserialize_as::<M, _, _>"
+    )]
+    w2: C::G1,
+    #[serde(skip)]
+    decoding: PhantomData<M>,
+}
+
+#[derive(::serde::Deserialize)]
+#[serde(bound = "")]
+struct EvaluationsSerde<C: Curve, M: Decoding> {
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    ql: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    qr: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    qm: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    qo: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    qc: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    s1: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    s2: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    s3: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    a: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    b: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    c: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    z: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    zw: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    t1w: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    t2w: C::Fr,
+    #[serde(
+        serialize_with = "fr::serialize",
+        deserialize_with = "fr::deserialize_as::<M, _, _>"
+    )]
+    inv: C::Fr,
+    #[serde(skip)]
+    decoding: PhantomData<M>,
+}
+
+impl<C: Curve, M: Decoding> From<ProofSerde<C, M>> for Proof<C> {
+    fn from(proof: ProofSerde<C, M>) -> Self {
+        let PolynomialsSerde { c1, c2, w1, w2, .. } = proof.polynomials;
+        let EvaluationsSerde {
+            ql,
+            qr,
+            qm,
+            qo,
+            qc,
+            s1,
+            s2,
+            s3,
+            a,
+            b,
+            c,
+            z,
+            zw,
+            t1w,
+            t2w,
+            inv,
+            ..
+        } = proof.evaluations;
+        Self {
+            polynomials: Polynomials { c1, c2, w1, w2 },
+            evaluations: Evaluations {
+                ql,
+                qr,
+                qm,
+                qo,
+                qc,
+                s1,
+                s2,
+                s3,
+                a,
+                b,
+                c,
+                z,
+                zw,
+                t1w,
+                t2w,
+                inv,
+            },
+        }
     }
 }
 
@@ -187,8 +711,13 @@ pub mod g1 {
 mod should {
     use ::serde::Deserialize;
     use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use substrate_bn::{AffineG1, AffineG2, Fq, Fq2, Fr, G1, G2};
 
     use super::super::*;
+    use super::{BigUint, Canonical, Decoding, Lenient};
+    use crate::proof::Polynomials;
 
     // Just because `json!` macro need `vec!` macro.
     #[cfg(feature = "std")]
@@ -200,6 +729,9 @@ mod should {
 
         let v: serde_json::Value = serde_json::from_str(&serialized).unwrap();
         let expected = serde_json::json!({
+        "protocol": "fflonk",
+        "curve": "bn128",
+        "nPublic": 1,
         "power": 24,
         "k1": "2",
         "k2": "3",
@@ -284,12 +816,270 @@ mod should {
     #[test]
     fn serialize_deserialize_in_a_non_human_readable_format() {
         let vk = VerificationKey::default();
-        let mut buffer = [0_u8; 600];
+        let mut buffer = [0_u8; 700];
         ciborium::into_writer(&vk, buffer.as_mut_slice()).unwrap();
         let other = ciborium::from_reader(buffer.as_slice()).unwrap();
         assert_eq!(vk, other);
     }
 
+    // rstest string literal cases need `std`.
+    #[cfg(feature = "std")]
+    #[rstest]
+    #[case::groth16(
+        r#""protocol":"fflonk""#,
+        r#""protocol":"groth16""#,
+        "Unsupported protocol 'groth16'"
+    )]
+    #[case::plonk(
+        r#""protocol":"fflonk""#,
+        r#""protocol":"plonk""#,
+        "Unsupported protocol 'plonk'"
+    )]
+    #[case::bls12381(
+        r#""curve":"bn128""#,
+        r#""curve":"bls12381""#,
+        "Unsupported curve 'bls12381'"
+    )]
+    fn reject_keys_for_other_protocols_or_curves(
+        #[case] field: &str,
+        #[case] replace: &str,
+        #[case] expected: &str,
+    ) {
+        let json = serde_json::to_string(&VerificationKey::default())
+            .unwrap()
+            .replace(field, replace);
+
+        let error = serde_json::from_str::<VerificationKey>(&json).unwrap_err();
+
+        assert!(
+            alloc::format!("{error}").contains(expected),
+            "Unexpected error: {error}"
+        );
+    }
+
+    #[rstest]
+    #[case::power(
+        r#""power":24"#,
+        r#""power":23"#,
+        "'w': it's not a primitive root of unity of order 8388608"
+    )]
+    #[case::k2(r#""k2":"3""#, r#""k2":"2""#, "'k2': it's not a coset separator")]
+    #[case::w3(
+        r#""w3":"21888242871839275217838484774961031246154997185409878258781734729429964517155""#,
+        r#""w3":"1""#,
+        "'w3': it's not a primitive root of unity of order 3"
+    )]
+    fn reject_inconsistent_keys(
+        #[case] field: &str,
+        #[case] replace: &str,
+        #[case] expected: &str,
+    ) {
+        let json = serde_json::to_string(&VerificationKey::default())
+            .unwrap()
+            .replace(field, replace);
+
+        let error = serde_json::from_str::<VerificationKey>(&json).unwrap_err();
+
+        assert!(
+            alloc::format!("{error}").contains(expected),
+            "Unexpected error: {error}"
+        );
+    }
+
+    const PROOF_JSON: &str = include_str!("../resources/bins/proof.json");
+
+    #[test]
+    fn accept_the_canonical_snarkjs_proof_and_key() {
+        let proof = serde_json::from_str::<Proof>(PROOF_JSON).unwrap();
+        let vk = VerificationKey::default();
+        let vk_json = serde_json::to_string(&vk).unwrap();
+
+        assert_eq!(
+            Strict(proof),
+            serde_json::from_str::<Strict<Proof>>(PROOF_JSON).unwrap()
+        );
+        assert_eq!(
+            Strict(vk),
+            serde_json::from_str::<Strict<VerificationKey>>(&vk_json).unwrap()
+        );
+    }
+
+    #[test]
+    fn accept_the_canonical_non_human_readable_encoding() {
+        let proof = serde_json::from_str::<Proof>(PROOF_JSON).unwrap();
+        let mut buffer = [0_u8; 1500];
+        ciborium::into_writer(&Strict(&proof), buffer.as_mut_slice()).unwrap();
+
+        let other: Strict<Proof> = ciborium::from_reader(buffer.as_slice()).unwrap();
+
+        assert_eq!(proof, other.0);
+    }
+
+    #[rstest]
+    #[case::leading_zeros(r#""qc": "0""#, r#""qc": "00""#)]
+    #[case::not_reduced(
+        r#""qc": "0""#,
+        r#""qc": "21888242871839275222246405745257275088548364400416034343698204186575808495617""#
+    )]
+    #[case::coordinate_leading_zeros(
+        r#""14556573045681247107321758464038638460236603165915844088653795891137636966580""#,
+        r#""014556573045681247107321758464038638460236603165915844088653795891137636966580""#
+    )]
+    #[case::z_leading_zeros(
+        "\"14556573045681247107321758464038638460236603165915844088653795891137636966580\",\n   \"1\"",
+        "\"14556573045681247107321758464038638460236603165915844088653795891137636966580\",\n   \"01\""
+    )]
+    #[case::infinity_with_coordinates(
+        "\"14556573045681247107321758464038638460236603165915844088653795891137636966580\",\n   \"1\"",
+        "\"14556573045681247107321758464038638460236603165915844088653795891137636966580\",\n   \"0\""
+    )]
+    fn reject_the_non_canonical_proofs_just_in_strict_mode(
+        #[case] field: &str,
+        #[case] replace: &str,
+    ) {
+        let json = PROOF_JSON.replace(field, replace);
+        assert_ne!(PROOF_JSON, json);
+
+        assert!(serde_json::from_str::<Proof>(&json).is_ok());
+        assert!(serde_json::from_str::<Strict<Proof>>(&json).is_err());
+    }
+
+    #[test]
+    fn accept_just_the_snarkjs_point_at_infinity_in_strict_mode() {
+        let proof = Proof {
+            polynomials: Polynomials {
+                c1: G1::zero(),
+                ..serde_json::from_str::<Proof>(PROOF_JSON)
+                    .unwrap()
+                    .polynomials
+            },
+            ..serde_json::from_str(PROOF_JSON).unwrap()
+        };
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(json.contains(r#""C1":["0","1","0"]"#));
+
+        assert_eq!(
+            proof,
+            serde_json::from_str::<Strict<Proof>>(&json).unwrap().0
+        );
+        assert!(serde_json::from_str::<Strict<Proof>>(
+            &json.replace(r#""C1":["0","1","0"]"#, r#""C1":["0","0","0"]"#)
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    #[case::leading_zeros(r#""k1":"2""#, r#""k1":"02""#)]
+    #[case::x2_z_leading_zeros(r#"["1","0"]]"#, r#"["01","0"]]"#)]
+    #[case::x2_z_imaginary_leading_zeros(r#"["1","0"]]"#, r#"["1","00"]]"#)]
+    fn reject_the_non_canonical_keys_just_in_strict_mode(
+        #[case] field: &str,
+        #[case] replace: &str,
+    ) {
+        let json = serde_json::to_string(&VerificationKey::default())
+            .unwrap()
+            .replace(field, replace);
+
+        assert!(serde_json::from_str::<VerificationKey>(&json).is_ok());
+        assert!(serde_json::from_str::<Strict<VerificationKey>>(&json).is_err());
+    }
+
+    #[test]
+    fn check_the_keys_in_strict_mode_too() {
+        let json = serde_json::to_string(&VerificationKey::default())
+            .unwrap()
+            .replace(r#""k2":"3""#, r#""k2":"2""#);
+
+        let error = serde_json::from_str::<Strict<VerificationKey>>(&json).unwrap_err();
+
+        assert!(
+            alloc::format!("{error}").contains("'k2': it's not a coset separator"),
+            "Unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn read_the_public_inputs_number() {
+        let json = serde_json::to_string(&VerificationKey::default())
+            .unwrap()
+            .replace(r#""nPublic":1"#, r#""nPublic":3"#);
+
+        let vk = serde_json::from_str::<VerificationKey>(&json).unwrap();
+
+        assert_eq!(3, vk.n_public);
+    }
+
+    #[test]
+    fn bound_the_decimal_length() {
+        let max = "9".repeat(BigUint::MAX_DECIMAL_LEN);
+        let longer = "0".repeat(BigUint::MAX_DECIMAL_LEN + 1);
+
+        assert_eq!(
+            Some(max.as_str()),
+            BigUint::from_decimal(&max)
+                .map(|v| v.to_decimal())
+                .as_deref()
+        );
+        assert!(BigUint::from_decimal(&longer).is_none());
+        assert!(serde_json::from_str::<BigUint>(&alloc::format!(r#""{}""#, longer)).is_err());
+    }
+
+    fn decimal(fq: Fq) -> String {
+        let mut bytes = [0; 32];
+        fq.into_u256().to_big_endian(&mut bytes).unwrap();
+        BigUint::new(bytes.to_vec()).to_decimal()
+    }
+
+    fn fq2_json(value: Fq2) -> String {
+        alloc::format!(
+            r#"["{}", "{}"]"#,
+            decimal(value.real()),
+            decimal(value.imaginary())
+        )
+    }
+
+    #[derive(Deserialize)]
+    #[serde(bound = "")]
+    struct Jacobian<M: Decoding>(
+        #[serde(deserialize_with = "super::g1::deserialize_as::<M, _, _>")] G1,
+        #[serde(deserialize_with = "super::g2::deserialize_as::<M, _, _>")] G2,
+        #[serde(skip)] core::marker::PhantomData<M>,
+    );
+
+    #[test]
+    fn accept_the_jacobian_coordinates_just_in_lenient_mode() {
+        let five = <Fr as Field>::from_u64(5);
+        let g1 = <G1 as Point>::generator() * five;
+        let g2 = <G2 as Point>::generator() * five;
+        let affine_g1 = AffineG1::from_jacobian(g1).unwrap();
+        let affine_g2 = AffineG2::from_jacobian(g2).unwrap();
+        let z = Fq::from_str("3").unwrap();
+        let z2 = Fq2::new(Fq::from_str("3").unwrap(), Fq::from_str("2").unwrap());
+        let json = alloc::format!(
+            r#"[["{}", "{}", "{}"], [{}, {}, {}]]"#,
+            decimal(affine_g1.x() * z * z),
+            decimal(affine_g1.y() * z * z * z),
+            decimal(z),
+            fq2_json(affine_g2.x() * z2 * z2),
+            fq2_json(affine_g2.y() * z2 * z2 * z2),
+            fq2_json(z2),
+        );
+
+        let Jacobian(lenient_g1, lenient_g2, _) =
+            serde_json::from_str::<Jacobian<Lenient>>(&json).unwrap();
+
+        assert_eq!(g1, lenient_g1);
+        assert_eq!(g2, lenient_g2);
+        assert!(serde_json::from_str::<Jacobian<Canonical>>(&json).is_err());
+    }
+
+    #[test]
+    fn check_the_jacobian_points() {
+        let json = r#"[["1", "3", "2"], [["1", "0"], ["2", "0"], ["3", "1"]]]"#;
+
+        assert!(serde_json::from_str::<Jacobian<Lenient>>(json).is_err());
+    }
+
     #[test]
     #[should_panic(expected = "Invalid G1 point")]
     fn raise_error_if_try_to_deserialize_an_invalid_g1_point() {
@@ -307,7 +1097,13 @@ mod should {
         let json = r#"[["1", "2"], ["3", "4"], ["5", "6"]]"#;
         #[derive(Deserialize)]
         #[allow(dead_code)]
-        struct Test(#[cfg_attr(feature = "serde", serde(with = "super::g2"))] G2);
+        struct Test(
+            #[cfg_attr(
+                feature = "serde",
+                serde(deserialize_with = "super::g2::deserialize_as::<super::Lenient, _, _>")
+            )]
+            G2,
+        );
 
         serde_json::from_str::<Test>(json).unwrap();
     }
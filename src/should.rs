@@ -16,8 +16,13 @@
 
 use rand::Rng;
 use rstest::{fixture, rstest};
+use substrate_bn::{Fq, Fq2, Fr, G1, G2};
 
-use crate::macros::u256s;
+use crate::{
+    macros::{u256, u256s},
+    proof::{Evaluations, Polynomials},
+    utils::{IntoFq as _, IntoFr as _},
+};
 
 use super::*;
 
@@ -58,20 +63,116 @@ fn valid_pubs() -> Public {
     u256!("0d69b94acdfaca5bacc248a60b35b925a2374644ce0c1205db68228c8921d9d9").into()
 }
 
+#[fixture]
+fn valid_proof_alt() -> Proof {
+    ProofData::from(u256s![
+        "2ecc31435ec6d6963d463c38ea5662d9c94a67e441e7bc611598ebcc59f57188",
+        "0768291fd5d95fcf02bce7e4fde1f048b843bbffab1f242904e82d443a4ebb61",
+        "150c3a4afdbb62d034320da390e3585a30ba13f4df73798b78e5a75655d3350d",
+        "19fca02cc5838405f9ae4177ac7117971af2cb5006d7a46436f644410d6e7c52",
+        "099f803c0f18d4b44fe22f3100d1fc80ccb7309fa7168f51bc64f3fc0f1dbd24",
+        "0b0573f3593238e56b23e75246d9d0f6f6a5cf824700667e3482ca9fecf74cdc",
+        "0b308e6a8f69dccb9ca00d540543441f7030928da766406a152427bdf31b4405",
+        "1b6b5198b34006f9ac34c6c857e450cc11f5c6b6d21119fe283738581c0ad8bd",
+        "0f8cbdbc574f64da884f6a02e00669f3eef10138266f3d7fa278aef1b1c60171",
+        "005c0c2b8b2429c5003c5ab24af44cb1ab81cdc96dcaf6004a0f74406bb10f45",
+        "233b13015cef8c40c491a7770efd0a8d8a64186d4f3827e74972bfc25b11f1f0",
+        "02550a5e253c923c5783026c7439601595477f1a212de449c64a8ae5e2fc0313",
+        "127bf9cd5146217e531196ce65ccef3249375450d6932151f923c39e6a735882",
+        "23d90f5bf230eee5a6cb6463f161602cc37fe538e2954ebef695b926b76e3fae",
+        "299c60c1952aa4b1f246204ac7c22c0156ede30aeb73444ee40d69c0f131fa47",
+        "1fdca090abfd38541c88ee73624657a695155748643f7834b80d1c0481079e67",
+        "0033817252b24575a4e6007f08f37c34462d5e9fd50b1e83ec8cfc86149400d5",
+        "19e224ee11831ac393e3a09730be6f385ae5c9e14446fde5069fea751fb6b482",
+        "11c85268b8017de7981eb1bd78526bc20d5f863ad3abe249728ca7b75b2146c1",
+        "254465d6100a911213d95f800779e74f6701b1dfa0b6660642108fd2c7cd2f13",
+        "1d9163eeebe9d8aabdf8d37fde4451f762be478d117688e0a6ed2648dbe025e8",
+        "2a4b13ee629a73d1efa6f269747506058746aa589bb961c1385bb2b30e0086f0",
+        "10ef87535f2137a04f19fe5aa7c4f348c32ce6f5b0b45bb503895673a8a51d7f",
+        "1b0228693fbfb38be718b04c9fdf116a97d7f30e670db84d21bb0d12fc576454",
+    ])
+    .try_into()
+    .unwrap()
+}
+
+#[fixture]
+fn valid_pubs_alt() -> Public {
+    u256!("15950a3fab52ee1557ac7b895deeca2eb27bacfc3b9e26a39b1875149680611d").into()
+}
+
 #[fixture]
 fn vk() -> VerificationKey {
     VerificationKey::default()
 }
 
+fn random<F: Field>() -> F {
+    F::from_be_bytes_reduced(&rand::thread_rng().gen())
+}
+
+/// Forge a valid proof by the trapdoor `tau` of the key's `X_2 = tau·G2`: all the proof
+/// values are random but the inverse and `W2 = (F - E - J) / (tau - y)`, that is the
+/// one that satisfy `e(F - E - J + y·W2, G2) = e(W2, X_2)`.
+/// The forged proofs check the verifier against itself, not against snarkjs: the only snarkjs
+/// vectors are the one public input `valid_proof` ones.
+fn forge_proof<C: Curve>(vk: &VerificationKey<C>, tau: C::Fr, pubs: &Public) -> Proof<C> {
+    let point = || C::G1::generator() * random();
+    let mut proof = Proof {
+        polynomials: Polynomials {
+            c1: point(),
+            c2: point(),
+            w1: point(),
+            w2: point(),
+        },
+        evaluations: Evaluations {
+            ql: random(),
+            qr: random(),
+            qm: random(),
+            qo: random(),
+            qc: random(),
+            s1: random(),
+            s2: random(),
+            s3: random(),
+            a: random(),
+            b: random(),
+            c: random(),
+            z: random(),
+            zw: random(),
+            t1w: random(),
+            t2w: random(),
+            inv: C::Fr::one(),
+        },
+    };
+    let vk = PreparedVerificationKey::from(vk);
+    // The inverse is not absorbed in the transcript
+    match proof.compute_challenges_and_fej::<Keccak256Transcript<C>>(&vk, pubs) {
+        Err(VerifyError::InvalidInverse { computed, .. }) => {
+            let mut bytes = [0; 32];
+            computed.to_big_endian(&mut bytes).unwrap();
+            proof.evaluations.inv = C::Fr::from_be_bytes(&bytes).unwrap().inverse().unwrap()
+        }
+        other => panic!("Unexpected result {other:?}"),
+    }
+    let (challenges, (f, e, j)) = proof
+        .compute_challenges_and_fej::<Keccak256Transcript<C>>(&vk, pubs)
+        .unwrap();
+    proof.polynomials.w2 = (f - e - j) * (tau - challenges.y).inverse().unwrap();
+    proof
+}
+
 #[rstest]
 fn compute_valid_check_paring(vk: VerificationKey, valid_proof: Proof, valid_pubs: Public) {
-    let vk_data = (&vk).into();
+    let vk_data = PreparedVerificationKey::from(vk);
 
-    let challenges = Challenges::build(&vk_data, &valid_proof, &valid_pubs);
-    let (inverse, l1) = challenges
-        .compute_inverse(&vk_data, valid_proof.evaluations.inv)
+    let challenges = Challenges::build::<Keccak256Transcript>(&vk_data, &valid_proof, &valid_pubs);
+    let (inverse, lagrange) = challenges
+        .compute_inverse(
+            &vk_data,
+            valid_pubs.len(),
+            Some(valid_proof.evaluations.inv),
+        )
         .unwrap();
-    let pi = Proof::compute_pi(&valid_pubs, l1);
+    let l1 = lagrange[0];
+    let pi = <Proof>::compute_pi(&valid_pubs, &lagrange);
     let r0 = valid_proof.compute_r0(&challenges, &inverse.li_s0_inv);
     let r1 = valid_proof.compute_r1(&challenges, pi, inverse.zh_inv, &inverse.li_s1_inv);
     let r2 = valid_proof.compute_r2(
@@ -82,10 +183,17 @@ fn compute_valid_check_paring(vk: VerificationKey, valid_proof: Proof, valid_pub
         &inverse.li_s2_inv,
     );
 
-    let (f, e, j) =
-        valid_proof.compute_fej(&vk, &challenges, r0, r1, r2, inverse.den_h1, inverse.den_h2);
+    let (f, e, j) = valid_proof.compute_fej(
+        &vk_data,
+        &challenges,
+        r0,
+        r1,
+        r2,
+        inverse.den_h1,
+        inverse.den_h2,
+    );
 
-    let result = valid_proof.check_paring(&challenges, &vk, f, e, j);
+    let result = valid_proof.check_paring(&challenges, &vk_data, f, e, j);
 
     assert!(result.is_ok())
 }
@@ -95,6 +203,1038 @@ fn verify_valid_proof(vk: VerificationKey, valid_proof: Proof, valid_pubs: Publi
     assert!(verify(&vk, &valid_proof, &valid_pubs).is_ok())
 }
 
+mod prepared_key {
+    use super::*;
+
+    #[rstest]
+    fn verify_many_proofs_with_the_same_prepared_key(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        valid_proof_alt: Proof,
+        valid_pubs_alt: Public,
+    ) {
+        let prepared = PreparedVerificationKey::from(vk);
+
+        verify_prepared(&prepared, &valid_proof, &valid_pubs).unwrap();
+        verify_prepared(&prepared, &valid_proof_alt, &valid_pubs_alt).unwrap();
+        assert!(verify_prepared(&prepared, &valid_proof, &valid_pubs_alt).is_err());
+    }
+
+    #[rstest]
+    fn verify_with_a_key_given_in_projective_coordinates(
+        mut vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        // Same points but with z != 1
+        let z = 7_u64.into_fq();
+        vk.c0 = G1::new(vk.c0.x() * z * z, vk.c0.y() * z * z * z, z);
+        let z = Fq2::new(3_u64.into_fq(), 5_u64.into_fq());
+        vk.x2 = G2::new(vk.x2.x() * z * z, vk.x2.y() * z * z * z, z);
+
+        verify_prepared(&vk.into(), &valid_proof, &valid_pubs).unwrap();
+    }
+
+    #[rstest]
+    fn cache_the_affine_points(vk: VerificationKey) {
+        let prepared = PreparedVerificationKey::from(&vk);
+
+        assert_eq!(Fq::one(), prepared.precomputed.c0.z());
+        assert_eq!(Fq2::one(), prepared.precomputed.x2.z());
+        assert_eq!(vk.c0, prepared.precomputed.c0);
+        assert_eq!(vk.x2, prepared.precomputed.x2);
+        assert_eq!(&vk, prepared.vk());
+    }
+}
+
+mod batch {
+    use super::*;
+
+    #[fixture]
+    fn batch(
+        valid_proof: Proof,
+        valid_pubs: Public,
+        valid_proof_alt: Proof,
+        valid_pubs_alt: Public,
+    ) -> Vec<(Proof, Public)> {
+        let mut batch = Vec::new();
+        for _ in 0..2 {
+            batch.push((valid_proof.clone(), valid_pubs.clone()));
+            batch.push((valid_proof_alt.clone(), valid_pubs_alt.clone()));
+        }
+        batch
+    }
+
+    #[rstest]
+    fn verify_valid_proofs(vk: VerificationKey, batch: Vec<(Proof, Public)>) {
+        verify_batch(&vk, &batch).unwrap();
+    }
+
+    #[rstest]
+    fn verify_a_single_proof(vk: VerificationKey, valid_proof: Proof, valid_pubs: Public) {
+        verify_batch(&vk, &[(valid_proof, valid_pubs)]).unwrap();
+    }
+
+    #[rstest]
+    fn verify_an_empty_batch(vk: VerificationKey) {
+        verify_batch(&vk, &[]).unwrap();
+    }
+
+    #[rstest]
+    fn identify_the_proof_that_fails_the_pairing(
+        vk: VerificationKey,
+        mut batch: Vec<(Proof, Public)>,
+        valid_pubs_alt: Public,
+    ) {
+        batch[2].1 = valid_pubs_alt;
+
+        let error = verify_batch(&vk, &batch).unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                BatchVerifyError::InvalidProof {
+                    index: 2,
+                    error: VerifyError::InvalidInverse { .. } | VerifyError::NotPairing
+                }
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    fn report_the_first_invalid_proof(vk: VerificationKey, batch: Vec<(Proof, Public)>) {
+        let mut batch = batch.iter().cycle().take(16).cloned().collect::<Vec<_>>();
+        for index in [13, 5, 11] {
+            batch[index].1 = Public::default();
+        }
+
+        let error = verify_batch(&vk, &batch).unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                BatchVerifyError::InvalidProof {
+                    index: 5,
+                    error: VerifyError::InvalidPublicInputsNumber { .. }
+                }
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    fn identify_the_proof_with_a_wrong_w2(vk: VerificationKey, mut batch: Vec<(Proof, Public)>) {
+        // W2 is not part of the transcript: only the pairing check can fail.
+        batch[3].0.polynomials.w2 = batch[3].0.polynomials.w2 * 2_u64.into_fr();
+
+        let error = verify_batch(&vk, &batch).unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                BatchVerifyError::InvalidProof {
+                    index: 3,
+                    error: VerifyError::NotPairing
+                }
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    fn reject_proofs_that_cancel_each_other_out(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        // Moving W2 of the same quantity in opposite directions breaks both proofs, but an
+        // unweighted sum of the pairing equations would still hold.
+        let delta = <G1 as Point>::generator() * 5_u64.into_fr();
+        let mut first = valid_proof.clone();
+        let mut second = valid_proof;
+        first.polynomials.w2 = first.polynomials.w2 + delta;
+        second.polynomials.w2 = second.polynomials.w2 - delta;
+
+        assert!(verify_batch(&vk, &[(first, valid_pubs.clone()), (second, valid_pubs)]).is_err());
+    }
+}
+
+mod many {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    /// A key with a random `X_2` trapdoor and `n` of its forged proofs.
+    pub(super) fn forged_key(n_public: usize, n: usize) -> (VerificationKey, Vec<(Proof, Public)>) {
+        forged_key_with(random(), n_public, n)
+    }
+
+    /// A key with the `tau` trapdoor and `n` of its forged proofs.
+    fn forged_key_with(
+        tau: Fr,
+        n_public: usize,
+        n: usize,
+    ) -> (VerificationKey, Vec<(Proof, Public)>) {
+        let vk = VerificationKey {
+            n_public,
+            x2: <G2 as Point>::generator() * tau,
+            ..Default::default()
+        };
+        let proofs = (0..n)
+            .map(|i| {
+                let pubs = (0..n_public as u64)
+                    .map(|v| U256::from(v + i as u64))
+                    .collect::<Public>();
+                (forge_proof(&vk, tau, &pubs), pubs)
+            })
+            .collect();
+        (vk, proofs)
+    }
+
+    #[fixture]
+    fn keys(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        valid_proof_alt: Proof,
+        valid_pubs_alt: Public,
+    ) -> Vec<(VerificationKey, Vec<(Proof, Public)>)> {
+        vec![
+            (
+                vk,
+                vec![(valid_proof, valid_pubs), (valid_proof_alt, valid_pubs_alt)],
+            ),
+            forged_key(1, 3),
+            forged_key(2, 2),
+        ]
+    }
+
+    /// Interleave the proofs of the different keys.
+    fn items(
+        keys: &[(VerificationKey, Vec<(Proof, Public)>)],
+    ) -> Vec<(&VerificationKey, &Proof, &Public)> {
+        let max = keys
+            .iter()
+            .map(|(_, proofs)| proofs.len())
+            .max()
+            .unwrap_or(0);
+        (0..max)
+            .flat_map(|i| {
+                keys.iter().filter_map(move |(vk, proofs)| {
+                    proofs.get(i).map(|(proof, pubs)| (vk, proof, pubs))
+                })
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn verify_the_proofs_of_many_keys(keys: Vec<(VerificationKey, Vec<(Proof, Public)>)>) {
+        verify_many(&items(&keys)).unwrap();
+    }
+
+    #[rstest]
+    fn verify_the_proofs_of_equal_keys(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let other = vk.clone();
+
+        verify_many(&[
+            (&vk, &valid_proof, &valid_pubs),
+            (&other, &valid_proof, &valid_pubs),
+        ])
+        .unwrap();
+    }
+
+    #[test]
+    fn verify_no_proofs() {
+        verify_many::<Bn254>(&[]).unwrap();
+    }
+
+    #[rstest]
+    fn identify_the_proof_with_a_wrong_w2(keys: Vec<(VerificationKey, Vec<(Proof, Public)>)>) {
+        let mut keys = keys;
+        // The second proof of the third key is the 6th item
+        keys[2].1[1].0.polynomials.w2 = keys[2].1[1].0.polynomials.w2 * 2_u64.into_fr();
+
+        let error = verify_many(&items(&keys)).unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                BatchVerifyError::InvalidProof {
+                    index: 5,
+                    error: VerifyError::NotPairing
+                }
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    fn report_the_invalid_public_inputs_index(
+        keys: Vec<(VerificationKey, Vec<(Proof, Public)>)>,
+        valid_pubs: Public,
+    ) {
+        let mut keys = keys;
+        keys[2].1[0].1 = valid_pubs;
+
+        let error = verify_many(&items(&keys)).unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                BatchVerifyError::InvalidProof {
+                    index: 2,
+                    error: VerifyError::InvalidPublicInputsNumber {
+                        expected: 2,
+                        actual: 1
+                    }
+                }
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    fn reject_the_proof_of_another_key(keys: Vec<(VerificationKey, Vec<(Proof, Public)>)>) {
+        let (proof, pubs) = &keys[1].1[0];
+
+        assert!(verify_many(&[(&keys[0].0, proof, pubs)]).is_err());
+    }
+
+    #[test]
+    fn reject_proofs_of_different_keys_that_cancel_each_other_out() {
+        let (first_vk, first) = forged_key(1, 1);
+        let (second_vk, second) = forged_key(1, 1);
+        let (mut first, mut second) = (first[0].clone(), second[0].clone());
+        // Both proofs are broken, but an unweighted sum of the G1 pairing terms would still
+        // hold.
+        let delta = <G1 as Point>::generator() * 5_u64.into_fr();
+        first.0.polynomials.w2 = first.0.polynomials.w2 + delta;
+        second.0.polynomials.w2 = second.0.polynomials.w2 - delta;
+
+        assert!(verify_many(&[
+            (&first_vk, &first.0, &first.1),
+            (&second_vk, &second.0, &second.1)
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn verify_the_proofs_of_keys_from_the_same_srs() {
+        let tau = random::<Fr>();
+        let keys = vec![forged_key_with(tau, 1, 2), forged_key_with(tau, 2, 2)];
+
+        verify_many(&items(&keys)).unwrap();
+    }
+
+    #[test]
+    fn reject_proofs_of_keys_from_the_same_srs_that_cancel_each_other_out() {
+        let tau = random::<Fr>();
+        let (first_vk, first) = forged_key_with(tau, 1, 1);
+        let (second_vk, second) = forged_key_with(tau, 2, 1);
+        let (mut first, mut second) = (first[0].clone(), second[0].clone());
+        let delta = <G1 as Point>::generator() * 5_u64.into_fr();
+        first.0.polynomials.w2 = first.0.polynomials.w2 + delta;
+        second.0.polynomials.w2 = second.0.polynomials.w2 - delta;
+
+        assert!(verify_many(&[
+            (&first_vk, &first.0, &first.1),
+            (&second_vk, &second.0, &second.1)
+        ])
+        .is_err());
+    }
+}
+
+mod accumulator {
+    use super::*;
+
+    #[rstest]
+    fn defer_the_pairing_check_of_a_valid_proof(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let vk = PreparedVerificationKey::from(vk);
+
+        let accumulator = verify_deferred(&vk, &valid_proof, &valid_pubs).unwrap();
+
+        assert_eq!(valid_proof.polynomials.w2, accumulator.rhs());
+        assert!(Bn254::pairing_check(&[
+            (accumulator.lhs(), PairingAccumulator::<Bn254>::g2_pair()),
+            (-accumulator.rhs(), accumulator.x2()),
+        ]));
+        accumulator.finalize().unwrap();
+    }
+
+    #[rstest]
+    fn accumulate_many_proofs(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        valid_proof_alt: Proof,
+        valid_pubs_alt: Public,
+    ) {
+        let vk = PreparedVerificationKey::from(vk);
+        let mut accumulator = PairingAccumulator::new(&vk);
+
+        for (proof, pubs) in [
+            (&valid_proof, &valid_pubs),
+            (&valid_proof_alt, &valid_pubs_alt),
+            (&valid_proof, &valid_pubs),
+        ] {
+            accumulator
+                .accumulate(&verify_deferred(&vk, proof, pubs).unwrap())
+                .unwrap();
+        }
+
+        accumulator.finalize().unwrap();
+    }
+
+    #[rstest]
+    fn hold_when_empty(vk: VerificationKey) {
+        PairingAccumulator::new(&PreparedVerificationKey::from(vk))
+            .finalize()
+            .unwrap();
+    }
+
+    #[rstest]
+    fn fail_if_a_proof_is_invalid(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        mut valid_proof_alt: Proof,
+        valid_pubs_alt: Public,
+    ) {
+        let vk = PreparedVerificationKey::from(vk);
+        // W2 is not part of the transcript: only the pairing check can fail.
+        valid_proof_alt.polynomials.w2 = valid_proof_alt.polynomials.w2 * 2_u64.into_fr();
+        let mut accumulator = verify_deferred(&vk, &valid_proof, &valid_pubs).unwrap();
+
+        accumulator
+            .accumulate(&verify_deferred(&vk, &valid_proof_alt, &valid_pubs_alt).unwrap())
+            .unwrap();
+
+        assert!(matches!(
+            accumulator.finalize(),
+            Err(VerifyError::NotPairing)
+        ));
+    }
+
+    #[rstest]
+    fn reject_proofs_that_cancel_each_other_out(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let vk = PreparedVerificationKey::from(vk);
+        let delta = <G1 as Point>::generator() * 5_u64.into_fr();
+        let mut first = valid_proof.clone();
+        let mut second = valid_proof;
+        first.polynomials.w2 = first.polynomials.w2 + delta;
+        second.polynomials.w2 = second.polynomials.w2 - delta;
+        let mut accumulator = verify_deferred(&vk, &first, &valid_pubs).unwrap();
+
+        accumulator
+            .accumulate(&verify_deferred(&vk, &second, &valid_pubs).unwrap())
+            .unwrap();
+
+        assert!(accumulator.finalize().is_err());
+    }
+
+    #[rstest]
+    fn reject_the_accumulation_of_different_keys(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let other = PreparedVerificationKey::from(VerificationKey {
+            x2: <G2 as Point>::generator() * random::<Fr>(),
+            ..vk.clone()
+        });
+        let mut accumulator = verify_deferred(
+            &PreparedVerificationKey::from(vk),
+            &valid_proof,
+            &valid_pubs,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Err(PairingAccumulatorError::DifferentKeys),
+            accumulator.accumulate(&PairingAccumulator::new(&other))
+        );
+    }
+
+    #[rstest]
+    fn report_the_errors_before_the_pairing(vk: VerificationKey, valid_proof: Proof) {
+        let vk = PreparedVerificationKey::from(vk);
+
+        let error = verify_deferred(&vk, &valid_proof, &Public::default()).unwrap_err();
+
+        assert!(
+            matches!(error, VerifyError::InvalidPublicInputsNumber { .. }),
+            "{error:?}"
+        );
+    }
+}
+
+mod without_inverse {
+    use super::*;
+
+    #[rstest]
+    fn compute_the_provided_inverse(vk: VerificationKey, valid_proof: Proof, valid_pubs: Public) {
+        let vk = PreparedVerificationKey::from(vk);
+        let challenges = Challenges::build::<Keccak256Transcript>(&vk, &valid_proof, &valid_pubs);
+        let n_public = valid_pubs.len();
+
+        assert_eq!(
+            challenges
+                .compute_inverse(&vk, n_public, Some(valid_proof.evaluations.inv))
+                .unwrap(),
+            challenges.compute_inverse(&vk, n_public, None).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn verify_a_valid_proof(vk: VerificationKey, valid_proof: Proof, valid_pubs: Public) {
+        let proof = ProofWithoutInverse::from(valid_proof);
+
+        verify_without_inverse(&vk, &proof, &valid_pubs).unwrap();
+    }
+
+    #[rstest]
+    fn verify_the_23_words_layout(vk: VerificationKey, valid_proof: Proof, valid_pubs: Public) {
+        let data = valid_proof.to_bytes();
+        let proof = <ProofWithoutInverse>::from_bytes(&data[..32 * 23]).unwrap();
+
+        verify_without_inverse(&vk, &proof, &valid_pubs).unwrap();
+    }
+
+    #[rstest]
+    fn not_need_a_valid_provided_inverse(
+        vk: VerificationKey,
+        mut valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        valid_proof.evaluations.inv = valid_proof.evaluations.inv + Fr::one();
+
+        verify_without_inverse(&vk, &valid_proof.into(), &valid_pubs).unwrap();
+    }
+
+    #[rstest]
+    fn reject_an_invalid_proof(vk: VerificationKey, mut valid_proof: Proof, valid_pubs: Public) {
+        valid_proof.evaluations.a = valid_proof.evaluations.a + Fr::one();
+
+        assert!(matches!(
+            verify_without_inverse(&vk, &valid_proof.into(), &valid_pubs),
+            Err(VerifyError::NotPairing)
+        ));
+    }
+
+    #[rstest]
+    fn report_the_errors_before_the_pairing(vk: VerificationKey, valid_proof: Proof) {
+        assert!(matches!(
+            verify_without_inverse(&vk, &valid_proof.into(), &Public::default()),
+            Err(VerifyError::InvalidPublicInputsNumber { .. })
+        ));
+    }
+
+    /// A key of a one-point domain: the S2 opening points collapse.
+    fn one_point_domain_key(mut vk: VerificationKey) -> VerificationKey {
+        vk.power = 0;
+        vk.w = Fr::one();
+        vk.wr = Fr::one();
+        vk
+    }
+
+    #[rstest]
+    fn not_panic_on_a_one_point_domain(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let vk = one_point_domain_key(vk);
+
+        assert!(verify_without_inverse(&vk, &valid_proof.into(), &valid_pubs).is_err());
+    }
+
+    #[rstest]
+    fn report_the_vanishing_denominators(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let mut vk = PreparedVerificationKey::from(one_point_domain_key(vk));
+        // Skip the key validation to reach the inverse computation.
+        vk.invalid_field = None;
+
+        assert!(matches!(
+            valid_proof.compute_trace_with::<Keccak256Transcript>(&vk, &valid_pubs, None),
+            Err(VerifyError::DegenerateChallenge { challenge: "xi" })
+        ));
+    }
+
+    #[test]
+    fn verify_forged_proofs() {
+        let (vk, proofs) = super::many::forged_key(3, 2);
+
+        for (proof, pubs) in proofs {
+            verify_without_inverse(&vk, &proof.into(), &pubs).unwrap();
+        }
+    }
+}
+
+mod trace {
+    use super::*;
+
+    #[rstest]
+    fn return_the_intermediate_values_of_a_valid_proof(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let prepared = PreparedVerificationKey::from(&vk);
+        let (challenges, (mut f, mut e, mut j)) = valid_proof
+            .compute_challenges_and_fej::<Keccak256Transcript>(&prepared, &valid_pubs)
+            .unwrap();
+        f.normalize();
+        e.normalize();
+        j.normalize();
+
+        let (result, trace) = verify_with_trace(&vk, &valid_proof, &valid_pubs);
+        let trace = trace.unwrap();
+
+        result.unwrap();
+        assert_eq!(challenges.beta, trace.beta);
+        assert_eq!(challenges.gamma, trace.gamma);
+        assert_eq!(challenges.xi, trace.xi);
+        assert_eq!(challenges.alpha, trace.alpha);
+        assert_eq!(challenges.y, trace.y);
+        assert_eq!(trace.xi.pow(1 << prepared.vk.power) - Fr::one(), trace.zh);
+        assert_eq!((f, e, j), (trace.f, trace.e, trace.j));
+        assert_eq!(Fq::one(), trace.f.z());
+    }
+
+    #[rstest]
+    fn return_the_trace_also_when_the_pairing_fails(
+        vk: VerificationKey,
+        mut valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let (_, valid_trace) = verify_with_trace(&vk, &valid_proof, &valid_pubs);
+        // W2 is not part of the transcript: only the pairing check can fail.
+        valid_proof.polynomials.w2 = valid_proof.polynomials.w2 * 2_u64.into_fr();
+
+        let (result, trace) = verify_with_trace(&vk, &valid_proof, &valid_pubs);
+
+        assert!(matches!(result, Err(VerifyError::NotPairing)));
+        assert_eq!(valid_trace, trace);
+    }
+
+    #[rstest]
+    fn not_return_the_trace_on_a_wrong_public_inputs_number(
+        vk: VerificationKey,
+        valid_proof: Proof,
+    ) {
+        let (result, trace) = verify_with_trace(&vk, &valid_proof, &Public::default());
+
+        assert!(matches!(
+            result,
+            Err(VerifyError::InvalidPublicInputsNumber { .. })
+        ));
+        assert_eq!(None, trace);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn serialize_the_trace_with_the_snarkjs_names(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let (_, trace) = verify_with_trace(&vk, &valid_proof, &valid_pubs);
+
+        let json = serde_json::to_value(&trace).unwrap();
+
+        for name in [
+            "beta", "gamma", "xi", "alpha", "y", "zh", "L1", "PI", "r0", "r1", "r2", "F", "E", "J",
+        ] {
+            assert!(json.get(name).is_some(), "Missing {name} in {json}");
+        }
+        assert_eq!(trace, serde_json::from_value(json).unwrap());
+    }
+}
+
+mod transcript {
+    use super::*;
+
+    /// Derive different challenges than the Keccak256 transcript.
+    #[derive(Default)]
+    struct ShiftedTranscript(Keccak256Transcript);
+
+    impl Transcript for ShiftedTranscript {
+        type Curve = Bn254;
+
+        fn absorb_scalar(&mut self, scalar: Fr) {
+            self.0.absorb_scalar(scalar)
+        }
+
+        fn absorb_g1(&mut self, point: G1) {
+            self.0.absorb_g1(point)
+        }
+
+        fn squeeze_challenge(&mut self) -> Fr {
+            self.0.squeeze_challenge() + Fr::one()
+        }
+    }
+
+    #[rstest]
+    fn use_keccak256_as_default_transcript(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        verify_with_transcript::<Keccak256Transcript>(&vk.into(), &valid_proof, &valid_pubs)
+            .unwrap();
+    }
+
+    #[rstest]
+    fn derive_the_challenges_from_the_given_transcript(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let vk = PreparedVerificationKey::from(vk);
+        let keccak = Challenges::build::<Keccak256Transcript>(&vk, &valid_proof, &valid_pubs);
+        let shifted = Challenges::build::<ShiftedTranscript>(&vk, &valid_proof, &valid_pubs);
+
+        assert_ne!(keccak.beta, shifted.beta);
+        assert_ne!(keccak.y, shifted.y);
+        assert!(
+            verify_with_transcript::<ShiftedTranscript>(&vk, &valid_proof, &valid_pubs).is_err()
+        );
+    }
+
+    #[cfg(feature = "poseidon")]
+    #[rstest]
+    fn reject_keccak256_proofs_with_the_poseidon_transcript(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        assert!(verify_with_transcript::<PoseidonTranscript>(
+            &vk.into(),
+            &valid_proof,
+            &valid_pubs
+        )
+        .is_err());
+    }
+}
+
+mod curves {
+    use super::*;
+
+    fn verify_forged_proofs<C: Curve>(mut vk: VerificationKey<C>) {
+        let tau = random::<C::Fr>();
+        vk.x2 = C::G2::generator() * tau;
+        let pubs = (0..vk.n_public as u64)
+            .map(substrate_bn::arith::U256::from)
+            .collect::<Public>();
+        let proof = forge_proof(&vk, tau, &pubs);
+        let mut invalid = proof.clone();
+        invalid.evaluations.a = invalid.evaluations.a + C::Fr::one();
+
+        verify(&vk, &proof, &pubs).unwrap();
+        assert_eq!(proof, Proof::<C>::from_bytes(&proof.to_bytes()).unwrap());
+        verify_batch(&vk, &[(proof.clone(), pubs.clone()), (proof, pubs.clone())]).unwrap();
+        assert!(verify(&vk, &invalid, &pubs).is_err());
+    }
+
+    #[test]
+    fn verify_proofs_on_bn254() {
+        verify_forged_proofs::<Bn254>(VerificationKey {
+            n_public: 2,
+            ..Default::default()
+        });
+    }
+
+    #[cfg(feature = "arkworks")]
+    mod arkworks {
+        use super::*;
+
+        #[test]
+        fn verify_proofs_on_arkworks() {
+            verify_forged_proofs::<ArkBn254>(
+                VerificationKey {
+                    n_public: 2,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+
+        #[rstest]
+        fn verify_the_converted_snarkjs_proofs(
+            vk: VerificationKey,
+            valid_proof: Proof,
+            valid_pubs: Public,
+        ) {
+            let vk = VerificationKey::<ArkBn254>::from(vk);
+            let proof = Proof::<ArkBn254>::from(valid_proof);
+
+            verify(&vk, &proof, &valid_pubs).unwrap();
+        }
+
+        #[rstest]
+        fn convert_proofs_and_keys_losslessly(vk: VerificationKey, valid_proof: Proof) {
+            let ark_vk = VerificationKey::<ArkBn254>::from(vk.clone());
+            let ark_proof = Proof::<ArkBn254>::from(valid_proof.clone());
+
+            assert_eq!(vk.to_bytes(), ark_vk.to_bytes());
+            assert_eq!(valid_proof.to_bytes(), ark_proof.to_bytes());
+            assert_eq!(vk, VerificationKey::from(ark_vk));
+            assert_eq!(valid_proof, Proof::from(ark_proof));
+        }
+    }
+
+    #[cfg(feature = "constant-time")]
+    mod constant_time {
+        use super::*;
+
+        #[test]
+        fn verify_proofs_on_constant_time() {
+            verify_forged_proofs::<CtBn254>(
+                VerificationKey {
+                    n_public: 2,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+
+        #[rstest]
+        fn verify_the_converted_snarkjs_proofs(
+            vk: VerificationKey,
+            valid_proof: Proof,
+            valid_pubs: Public,
+        ) {
+            let vk = VerificationKey::<CtBn254>::from(vk);
+            let proof = Proof::<CtBn254>::from(valid_proof);
+
+            verify(&vk, &proof, &valid_pubs).unwrap();
+        }
+
+        #[rstest]
+        fn compute_the_same_trace(vk: VerificationKey, valid_proof: Proof, valid_pubs: Public) {
+            let (result, trace) = verify_with_trace(&vk, &valid_proof, &valid_pubs);
+            let (ct_result, ct_trace) = verify_with_trace(
+                &VerificationKey::<CtBn254>::from(vk),
+                &Proof::<CtBn254>::from(valid_proof),
+                &valid_pubs,
+            );
+            let (trace, ct_trace) = (trace.unwrap(), ct_trace.unwrap());
+
+            result.unwrap();
+            ct_result.unwrap();
+            assert_eq!(trace.y.to_be_bytes(), ct_trace.y.to_be_bytes());
+            assert_eq!(trace.pi.to_be_bytes(), ct_trace.pi.to_be_bytes());
+            assert_eq!(trace.r2.to_be_bytes(), ct_trace.r2.to_be_bytes());
+            assert_eq!(trace.j, ct_trace.j.0);
+        }
+
+        #[rstest]
+        fn convert_proofs_and_keys_losslessly(vk: VerificationKey, valid_proof: Proof) {
+            let ct_vk = VerificationKey::<CtBn254>::from(vk.clone());
+            let ct_proof = Proof::<CtBn254>::from(valid_proof.clone());
+
+            assert_eq!(vk.to_bytes(), ct_vk.to_bytes());
+            assert_eq!(valid_proof.to_bytes(), ct_proof.to_bytes());
+            assert_eq!(vk, VerificationKey::from(ct_vk));
+            assert_eq!(valid_proof, Proof::from(ct_proof));
+        }
+    }
+}
+
+mod public_inputs {
+    use super::*;
+
+    #[fixture]
+    fn many_pubs() -> Public {
+        u256s![
+            "0d69b94acdfaca5bacc248a60b35b925a2374644ce0c1205db68228c8921d9d9",
+            "110d778eaf8b8ef7ac10f8ac239a14df0eb292a8d1b71340d527b26301a9ab08",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        ]
+        .into()
+    }
+
+    /// Compute the inverse of all the batched denominators as the prover does.
+    fn batched_inverse(
+        vk_data: &PreparedVerificationKey,
+        challenges: &Challenges<Bn254>,
+        n_public: usize,
+    ) -> Fr {
+        let product = [
+            challenges.zh,
+            challenges.compute_den_h1_base(),
+            challenges.compute_den_h2_base(),
+        ]
+        .into_iter()
+        .chain(challenges.compute_li_s0())
+        .chain(challenges.compute_li_s1())
+        .chain(challenges.compute_li_s2(vk_data.vk.w))
+        .chain(
+            lagrange_roots(vk_data.vk.w, n_public.max(1))
+                .into_iter()
+                .map(|root| challenges.compute_eval_l_base(vk_data.precomputed.n, root)),
+        )
+        .fold(Fr::one(), |acc, v| acc * v);
+        product.inverse().unwrap()
+    }
+
+    #[rstest]
+    fn compute_lagrange_evaluations_for_each_public_input(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        many_pubs: Public,
+    ) {
+        let vk_data = PreparedVerificationKey::from(&vk);
+        let challenges =
+            Challenges::build::<Keccak256Transcript>(&vk_data, &valid_proof, &many_pubs);
+        let inv = batched_inverse(&vk_data, &challenges, many_pubs.len());
+
+        let (_, lagrange) = challenges
+            .compute_inverse(&vk_data, many_pubs.len(), Some(inv))
+            .unwrap();
+
+        assert_eq!(many_pubs.len(), lagrange.len());
+        // The closed form `L_i(xi) = w^i·Z_H(xi) / (n·(xi - w^i))`: not a snarkjs vector.
+        let mut root = Fr::one();
+        for l in lagrange {
+            let expected = root
+                * challenges.zh
+                * (vk_data.precomputed.n * (challenges.xi - root))
+                    .inverse()
+                    .unwrap();
+            assert_eq!(expected, l);
+            root = root * vk.w;
+        }
+    }
+
+    #[rstest]
+    fn compute_at_least_l1_also_without_public_inputs(vk: VerificationKey, valid_proof: Proof) {
+        let vk_data = PreparedVerificationKey::from(&vk);
+        let pubs = Public::default();
+        let challenges = Challenges::build::<Keccak256Transcript>(&vk_data, &valid_proof, &pubs);
+        let inv = batched_inverse(&vk_data, &challenges, pubs.len());
+
+        let (_, lagrange) = challenges.compute_inverse(&vk_data, 0, Some(inv)).unwrap();
+
+        assert_eq!(1, lagrange.len());
+        assert_eq!(Fr::zero(), <Proof>::compute_pi(&pubs, &lagrange));
+    }
+
+    #[rstest]
+    fn compute_pi_as_the_sum_over_all_public_inputs(many_pubs: Public) {
+        let lagrange = [3_u64.into_fr(), 5_u64.into_fr(), 7_u64.into_fr()];
+
+        let expected = -(lagrange[0] * many_pubs.as_slice()[0].into_fr()
+            + lagrange[1] * many_pubs.as_slice()[1].into_fr()
+            + lagrange[2] * many_pubs.as_slice()[2].into_fr());
+
+        assert_eq!(expected, <Proof>::compute_pi(&many_pubs, &lagrange));
+    }
+
+    #[rstest]
+    fn absorb_all_public_inputs_in_the_transcript(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        many_pubs: Public,
+    ) {
+        let vk_data = PreparedVerificationKey::from(&vk);
+
+        let single = Challenges::build::<Keccak256Transcript>(&vk_data, &valid_proof, &valid_pubs);
+        let many = Challenges::build::<Keccak256Transcript>(&vk_data, &valid_proof, &many_pubs);
+
+        assert_ne!(single.beta, many.beta);
+    }
+
+    #[rstest]
+    fn read_public_inputs_from_concatenated_bytes(many_pubs: Public) {
+        let bytes = many_pubs
+            .as_slice()
+            .iter()
+            .flat_map(|v| crate::utils::IntoBytes::into_bytes(*v))
+            .collect::<Vec<_>>();
+
+        assert_eq!(many_pubs, Public::try_from(bytes.as_slice()).unwrap());
+    }
+
+    #[rstest]
+    #[case::too_short(31)]
+    #[case::not_aligned(65)]
+    fn reject_public_inputs_bytes_not_multiple_of_32(#[case] len: usize) {
+        let bytes = [0_u8; 65];
+
+        assert!(Public::try_from(&bytes[..len]).is_err());
+    }
+
+    #[rstest]
+    fn reject_a_valid_proof_with_more_public_inputs(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        let pubs = valid_pubs
+            .as_slice()
+            .iter()
+            .copied()
+            .chain([U256::one()])
+            .collect::<Public>();
+
+        assert!(matches!(
+            verify(&vk, &valid_proof, &pubs),
+            Err(VerifyError::InvalidPublicInputsNumber {
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+
+    #[rstest]
+    fn reject_a_valid_proof_without_public_inputs(vk: VerificationKey, valid_proof: Proof) {
+        assert!(matches!(
+            verify(&vk, &valid_proof, &Public::default()),
+            Err(VerifyError::InvalidPublicInputsNumber {
+                expected: 1,
+                actual: 0
+            })
+        ));
+    }
+
+    #[rstest]
+    fn reject_public_inputs_not_matching_the_key_declared_number(
+        mut vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+    ) {
+        vk.n_public = 2;
+
+        assert!(matches!(
+            verify(&vk, &valid_proof, &valid_pubs),
+            Err(VerifyError::InvalidPublicInputsNumber {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg(feature = "serde")]
 mod verify_valid_deserialized_proof {
@@ -319,4 +1459,95 @@ mod reject {
         proof.evaluations.inv = Fr::random(&mut rng);
         verify(&vk, &proof, &valid_pubs).unwrap()
     }
+    /// The expected offending field name
+    struct Expected(&'static str);
+
+    #[rstest]
+    #[case::power(|vk: &mut VerificationKey| vk.power = 29, Expected("power"))]
+    #[case::k1(|vk: &mut VerificationKey| vk.k1 = Fr::one(), Expected("k1"))]
+    #[case::k2(|vk: &mut VerificationKey| vk.k2 = vk.k1, Expected("k2"))]
+    #[case::w(|vk: &mut VerificationKey| vk.w = vk.w * vk.w, Expected("w"))]
+    #[case::w3(|vk: &mut VerificationKey| vk.w3 = Fr::one(), Expected("w3"))]
+    #[case::w4(|vk: &mut VerificationKey| vk.w4 = vk.w4 * vk.w4, Expected("w4"))]
+    #[case::w8(|vk: &mut VerificationKey| vk.w8 = vk.w8 * vk.w8, Expected("w8"))]
+    #[case::wr(|vk: &mut VerificationKey| vk.wr = vk.wr * 2_u64.into_fr(), Expected("wr"))]
+    #[case::c0(|vk: &mut VerificationKey| vk.c0 = G1::zero(), Expected("c0"))]
+    #[case::x2(|vk: &mut VerificationKey| vk.x2 = G2::zero(), Expected("x2"))]
+    fn a_malformed_vk(
+        mut vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        #[case] change: fn(&mut VerificationKey),
+        #[case] expected: Expected,
+    ) {
+        change(&mut vk);
+
+        let error = verify(&vk, &valid_proof, &valid_pubs).unwrap_err();
+
+        assert!(
+            matches!(error, VerifyError::InvalidVerificationKey { field } if field == expected.0),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    fn a_public_input_not_in_the_field(vk: VerificationKey, valid_proof: Proof) {
+        let value = u256!("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+
+        let error = verify(&vk, &valid_proof, &value.into()).unwrap_err();
+
+        assert!(
+            matches!(error, VerifyError::InvalidPublicInput { index: 0, value: v } if v == value),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    fn a_proof_commitment_at_infinity(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        #[values(C1, C2, W1, W2)] change: ProofFields,
+    ) {
+        let mut proof = valid_proof;
+        let point = match change {
+            C1 => &mut proof.polynomials.c1,
+            C2 => &mut proof.polynomials.c2,
+            W1 => &mut proof.polynomials.w1,
+            _ => &mut proof.polynomials.w2,
+        };
+        *point = G1::zero();
+
+        let error = verify(&vk, &proof, &valid_pubs).unwrap_err();
+
+        assert!(
+            matches!(error, VerifyError::PointAtInfinity { field } if field == change.str()),
+            "{error:?}"
+        );
+    }
+
+    #[rstest]
+    #[case::xi_zero(|c: &mut Challenges<Bn254>| c.xi = Fr::zero(), Expected("xi"))]
+    #[case::xi_domain_root(|c: &mut Challenges<Bn254>| c.zh = Fr::zero(), Expected("zh"))]
+    #[case::y_opening_point(|c: &mut Challenges<Bn254>| c.y = c.h0_w8[5], Expected("y"))]
+    #[case::y_h3_opening_point(|c: &mut Challenges<Bn254>| c.y = c.h3_w3[2], Expected("y"))]
+    fn degenerate_challenges(
+        vk: VerificationKey,
+        valid_proof: Proof,
+        valid_pubs: Public,
+        #[case] change: fn(&mut Challenges<Bn254>),
+        #[case] expected: Expected,
+    ) {
+        let mut challenges =
+            Challenges::build::<Keccak256Transcript>(&vk.into(), &valid_proof, &valid_pubs);
+        challenges.check_degenerate().unwrap();
+        change(&mut challenges);
+
+        let error = challenges.check_degenerate().unwrap_err();
+
+        assert!(
+            matches!(error, VerifyError::DegenerateChallenge { challenge } if challenge == expected.0),
+            "{error:?}"
+        );
+    }
 }
@@ -0,0 +1,225 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Generate the Solidity verifier contract for a verification key. The contract follows
+//! the same steps of [`crate::verify`]: the transcript is the one used to build the
+//! challenges and the proof is the `bytes32[24]` with the [`crate::ProofRawData`] layout.
+//!
+//! `tests/solidity.rs` compiles the contract with `solc` and runs `verifyProof` in revm on
+//! the `resources/bins` proof and on a tampered one. It needs `solc`, so it's ignored by
+//! default and the CI runs it: until it passes there the generator is experimental.
+
+#![cfg(feature = "solidity")]
+
+use alloc::{format, string::String};
+use core::fmt::Write as _;
+use substrate_bn::{arith::U256, AffineG1, AffineG2, Fq, Fq2, Fr};
+
+use crate::{PrecomputedData, VerificationKey};
+
+const TEMPLATE: &str = include_str!("solidity/FflonkVerifier.sol");
+
+/// Render the Solidity source of a `FflonkVerifier` contract that verifies the proofs
+/// for the given verification key with `verifyProof(bytes32[24], uint256[nPublic])`.
+pub fn solidity_verifier(vk: &VerificationKey) -> String {
+    let precomputed = PrecomputedData::from(vk);
+    let (c0_x, c0_y) = AffineG1::from_jacobian(vk.c0)
+        .map(|p| (p.x(), p.y()))
+        .unwrap_or((Fq::zero(), Fq::zero()));
+    let (x2_x, x2_y) = AffineG2::from_jacobian(vk.x2)
+        .map(|p| (p.x(), p.y()))
+        .unwrap_or((Fq2::zero(), Fq2::zero()));
+    let fr = |v: Fr| hex(v.into_u256());
+    let fq = |v: Fq| hex(v.into_u256());
+
+    [
+        ("{{VERIFY_PROOF}}", verify_proof_function(vk.n_public)),
+        ("{{N_PUBLIC}}", format!("{}", vk.n_public)),
+        ("{{POWER}}", format!("{}", vk.power)),
+        ("{{N}}", fr(precomputed.n)),
+        ("{{K1}}", fr(vk.k1)),
+        ("{{K2}}", fr(vk.k2)),
+        ("{{W}}", fr(vk.w)),
+        ("{{W3}}", fr(precomputed.w3[0])),
+        ("{{W3_2}}", fr(precomputed.w3[1])),
+        ("{{W4}}", fr(precomputed.w4[0])),
+        ("{{W4_2}}", fr(precomputed.w4[1])),
+        ("{{W4_3}}", fr(precomputed.w4[2])),
+        ("{{W8}}", fr(precomputed.w8[0])),
+        ("{{W8_2}}", fr(precomputed.w8[1])),
+        ("{{W8_3}}", fr(precomputed.w8[2])),
+        ("{{W8_4}}", fr(precomputed.w8[3])),
+        ("{{W8_5}}", fr(precomputed.w8[4])),
+        ("{{W8_6}}", fr(precomputed.w8[5])),
+        ("{{W8_7}}", fr(precomputed.w8[6])),
+        ("{{WR}}", fr(vk.wr)),
+        ("{{C0X}}", fq(c0_x)),
+        ("{{C0Y}}", fq(c0_y)),
+        ("{{X2X1}}", fq(x2_x.real())),
+        ("{{X2X2}}", fq(x2_x.imaginary())),
+        ("{{X2Y1}}", fq(x2_y.real())),
+        ("{{X2Y2}}", fq(x2_y.imaginary())),
+    ]
+    .into_iter()
+    .fold(String::from(TEMPLATE), |source, (placeholder, value)| {
+        source.replace(placeholder, &value)
+    })
+}
+
+/// Solidity doesn't support zero length arrays: without public inputs the contract
+/// entry point takes just the proof.
+fn verify_proof_function(n_public: usize) -> String {
+    if n_public == 0 {
+        String::from(
+            r#"/// Verify the `proof` for a circuit without public inputs.
+    function verifyProof(bytes32[24] calldata proof) public view returns (bool) {
+        return verify(proof, new uint256[](0));
+    }"#,
+        )
+    } else {
+        format!(
+            r#"/// Verify the `proof` against the public inputs `pubSignals`.
+    function verifyProof(bytes32[24] calldata proof, uint256[{n_public}] calldata pubSignals)
+        public
+        view
+        returns (bool)
+    {{
+        uint256[] memory pubs = new uint256[](N_PUBLIC);
+        for (uint256 i = 0; i < N_PUBLIC; i++) {{
+            pubs[i] = pubSignals[i];
+        }}
+        return verify(proof, pubs);
+    }}"#
+        )
+    }
+}
+
+fn hex(value: U256) -> String {
+    let mut bytes = [0_u8; 32];
+    value
+        .to_big_endian(&mut bytes)
+        .expect("Cannot fail: is a 32-byte array");
+    bytes.iter().fold(String::from("0x"), |mut out, b| {
+        write!(out, "{b:02x}").expect("Cannot fail to write on a string");
+        out
+    })
+}
+
+#[cfg(test)]
+mod should {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::utils::IntoFr;
+
+    fn constant(source: &str, name: &str) -> U256 {
+        let prefix = format!("uint256 constant {name} = ");
+        let value = source
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(prefix.as_str()))
+            .unwrap_or_else(|| panic!("Constant {name} not found"))
+            .trim_end_matches(';');
+        match value.strip_prefix("0x") {
+            Some(hex) => U256::from_slice(&hex::decode(hex).unwrap()).unwrap(),
+            None => U256::from(value.parse::<u64>().unwrap()),
+        }
+    }
+
+    #[test]
+    fn replace_all_placeholders() {
+        let source = solidity_verifier(&VerificationKey::default());
+
+        assert!(!source.contains("{{"), "Placeholder left in:\n{source}");
+    }
+
+    #[test]
+    fn render_the_derived_roots_and_domain_size() {
+        let vk = VerificationKey::default();
+        let source = solidity_verifier(&vk);
+
+        assert_eq!(U256::from(1 << 24), constant(&source, "N"));
+        assert_eq!((vk.w3 * vk.w3).into_u256(), constant(&source, "W3_2"));
+        assert_eq!(
+            (vk.w4 * vk.w4 * vk.w4).into_u256(),
+            constant(&source, "W4_3")
+        );
+        assert_eq!(
+            vk.w8.pow(7_u64.into_fr()).into_u256(),
+            constant(&source, "W8_7")
+        );
+    }
+
+    #[rstest]
+    fn render_the_public_inputs_signature(#[values(0, 1, 3)] n_public: usize) {
+        let expected = match n_public {
+            0 => String::from("function verifyProof(bytes32[24] calldata proof) public"),
+            n => format!("uint256[{n}] calldata pubSignals"),
+        };
+        let vk = VerificationKey {
+            n_public,
+            ..Default::default()
+        };
+
+        let source = solidity_verifier(&vk);
+
+        assert!(source.contains(&expected));
+        assert_eq!(U256::from(n_public as u64), constant(&source, "N_PUBLIC"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn render_the_json_verification_key_constants(
+        #[values(
+            include_str!("../resources/bins/verification_key.json"),
+            include_str!("../resources/bins/zksync_vk.json")
+        )]
+        json: &str,
+    ) {
+        let vk: VerificationKey = serde_json::from_str(json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(json).unwrap();
+        let source = solidity_verifier(&vk);
+        let decimal = |value: &serde_json::Value| {
+            let value = ethnum::U256::from_str_radix(value.as_str().unwrap(), 10).unwrap();
+            U256([value.0[0], value.0[1]])
+        };
+
+        assert_eq!(
+            U256::from(json["nPublic"].as_u64().unwrap()),
+            constant(&source, "N_PUBLIC")
+        );
+        assert_eq!(
+            U256::from(json["power"].as_u64().unwrap()),
+            constant(&source, "POWER")
+        );
+        for (name, field) in [
+            ("K1", "k1"),
+            ("K2", "k2"),
+            ("W1", "w"),
+            ("W3", "w3"),
+            ("W4", "w4"),
+            ("W8_1", "w8"),
+            ("WR", "wr"),
+        ] {
+            assert_eq!(decimal(&json[field]), constant(&source, name), "{name}");
+        }
+        assert_eq!(decimal(&json["C0"][0]), constant(&source, "C0X"));
+        assert_eq!(decimal(&json["C0"][1]), constant(&source, "C0Y"));
+        assert_eq!(decimal(&json["X_2"][0][0]), constant(&source, "X2X1"));
+        assert_eq!(decimal(&json["X_2"][0][1]), constant(&source, "X2X2"));
+        assert_eq!(decimal(&json["X_2"][1][0]), constant(&source, "X2Y1"));
+        assert_eq!(decimal(&json["X_2"][1][1]), constant(&source, "X2Y2"));
+    }
+}
@@ -0,0 +1,503 @@
+// SPDX-License-Identifier: GPL-3.0
+//
+// Generated by fflonk_verifier: the verification key is hardcoded in the constants below.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+pragma solidity >=0.8.4 <0.9.0;
+
+/// @title Fflonk verifier
+/// @notice Verify the fflonk proofs of a circuit with {{N_PUBLIC}} public inputs. The proof is
+/// the 24 big-endian words of `ProofRawData`: C1, C2, W1, W2 affine points followed by
+/// ql, qr, qm, qo, qc, s1, s2, s3, a, b, c, z, zw, t1w, t2w evaluations and the batched inverse.
+contract FflonkVerifier {
+    // Scalar field size
+    uint256 constant R = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+    // Base field size
+    uint256 constant Q = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+
+    // Verification key
+    uint256 constant N_PUBLIC = {{N_PUBLIC}};
+    uint256 constant POWER = {{POWER}};
+    uint256 constant N = {{N}};
+    uint256 constant K1 = {{K1}};
+    uint256 constant K2 = {{K2}};
+    uint256 constant W1 = {{W}};
+    uint256 constant W3 = {{W3}};
+    uint256 constant W3_2 = {{W3_2}};
+    uint256 constant W4 = {{W4}};
+    uint256 constant W4_2 = {{W4_2}};
+    uint256 constant W4_3 = {{W4_3}};
+    uint256 constant W8_1 = {{W8}};
+    uint256 constant W8_2 = {{W8_2}};
+    uint256 constant W8_3 = {{W8_3}};
+    uint256 constant W8_4 = {{W8_4}};
+    uint256 constant W8_5 = {{W8_5}};
+    uint256 constant W8_6 = {{W8_6}};
+    uint256 constant W8_7 = {{W8_7}};
+    uint256 constant WR = {{WR}};
+    uint256 constant C0X = {{C0X}};
+    uint256 constant C0Y = {{C0Y}};
+    uint256 constant X2X1 = {{X2X1}};
+    uint256 constant X2X2 = {{X2X2}};
+    uint256 constant X2Y1 = {{X2Y1}};
+    uint256 constant X2Y2 = {{X2Y2}};
+
+    // Curve generators
+    uint256 constant G1X = 1;
+    uint256 constant G1Y = 2;
+    uint256 constant G2X1 = 0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed;
+    uint256 constant G2X2 = 0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2;
+    uint256 constant G2Y1 = 0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa;
+    uint256 constant G2Y2 = 0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b;
+
+    // Proof words positions
+    uint256 constant P_C1 = 0;
+    uint256 constant P_C2 = 2;
+    uint256 constant P_W1 = 4;
+    uint256 constant P_W2 = 6;
+    uint256 constant P_QL = 8;
+    uint256 constant P_QR = 9;
+    uint256 constant P_QM = 10;
+    uint256 constant P_QO = 11;
+    uint256 constant P_QC = 12;
+    uint256 constant P_S1 = 13;
+    uint256 constant P_S2 = 14;
+    uint256 constant P_S3 = 15;
+    uint256 constant P_A = 16;
+    uint256 constant P_B = 17;
+    uint256 constant P_C = 18;
+    uint256 constant P_Z = 19;
+    uint256 constant P_ZW = 20;
+    uint256 constant P_T1W = 21;
+    uint256 constant P_T2W = 22;
+    uint256 constant P_INV = 23;
+
+    struct Challenges {
+        uint256 beta;
+        uint256 gamma;
+        uint256 xiSeed;
+        uint256 xi;
+        uint256 zh;
+        uint256 alpha;
+        uint256 y;
+        uint256[8] h0w8;
+        uint256[4] h1w4;
+        uint256[3] h2w3;
+        uint256[3] h3w3;
+    }
+
+    struct Inverses {
+        uint256 zhInv;
+        uint256 denH1;
+        uint256 denH2;
+        uint256[8] liS0;
+        uint256[4] liS1;
+        uint256[6] liS2;
+        // The Lagrange polynomials evaluations L_i(xi)
+        uint256[] lagrange;
+    }
+
+    {{VERIFY_PROOF}}
+
+    function verify(bytes32[24] calldata proof, uint256[] memory pubs) internal view returns (bool) {
+        if (!checkInput(proof, pubs)) {
+            return false;
+        }
+        Challenges memory ch = computeChallenges(proof, pubs);
+        (bool ok, Inverses memory inv) = computeInverses(proof, ch);
+        if (!ok) {
+            return false;
+        }
+        uint256 r0 = computeR0(proof, ch, inv);
+        uint256 r1 = computeR1(proof, ch, inv, computePi(pubs, inv));
+        uint256 r2 = computeR2(proof, ch, inv);
+        return checkPairing(proof, ch, inv, r0, r1, r2);
+    }
+
+    function checkInput(bytes32[24] calldata proof, uint256[] memory pubs) internal pure returns (bool) {
+        for (uint256 i = 0; i < P_QL; i++) {
+            if (uint256(proof[i]) >= Q) {
+                return false;
+            }
+        }
+        for (uint256 i = P_QL; i < 24; i++) {
+            if (uint256(proof[i]) >= R) {
+                return false;
+            }
+        }
+        for (uint256 i = 0; i < pubs.length; i++) {
+            if (pubs[i] >= R) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    function computeChallenges(bytes32[24] calldata proof, uint256[] memory pubs)
+        internal
+        pure
+        returns (Challenges memory ch)
+    {
+        ch.beta = uint256(keccak256(abi.encodePacked(C0X, C0Y, pubs, proof[P_C1], proof[P_C1 + 1]))) % R;
+        ch.gamma = uint256(keccak256(abi.encodePacked(ch.beta))) % R;
+        ch.xiSeed = uint256(keccak256(abi.encodePacked(ch.gamma, proof[P_C2], proof[P_C2 + 1]))) % R;
+
+        uint256 xiSeed2 = mulmod(ch.xiSeed, ch.xiSeed, R);
+        uint256 h0 = mulmod(ch.xiSeed, xiSeed2, R);
+        ch.h0w8[0] = h0;
+        ch.h0w8[1] = mulmod(h0, W8_1, R);
+        ch.h0w8[2] = mulmod(h0, W8_2, R);
+        ch.h0w8[3] = mulmod(h0, W8_3, R);
+        ch.h0w8[4] = mulmod(h0, W8_4, R);
+        ch.h0w8[5] = mulmod(h0, W8_5, R);
+        ch.h0w8[6] = mulmod(h0, W8_6, R);
+        ch.h0w8[7] = mulmod(h0, W8_7, R);
+        uint256 h1 = mulmod(h0, h0, R);
+        ch.h1w4 = [h1, mulmod(h1, W4, R), mulmod(h1, W4_2, R), mulmod(h1, W4_3, R)];
+        uint256 h2 = mulmod(h1, xiSeed2, R);
+        ch.h2w3 = [h2, mulmod(h2, W3, R), mulmod(h2, W3_2, R)];
+        uint256 h3 = mulmod(h2, WR, R);
+        ch.h3w3 = [h3, mulmod(h3, W3, R), mulmod(h3, W3_2, R)];
+
+        ch.xi = mulmod(mulmod(h2, h2, R), h2, R);
+        uint256 xiN = ch.xi;
+        for (uint256 i = 0; i < POWER; i++) {
+            xiN = mulmod(xiN, xiN, R);
+        }
+        ch.zh = addmod(xiN, R - 1, R);
+
+        ch.alpha = computeAlpha(proof, ch.xiSeed);
+        ch.y = uint256(keccak256(abi.encodePacked(ch.alpha, proof[P_W1], proof[P_W1 + 1]))) % R;
+    }
+
+    /// alpha = keccak(xiSeed, ql, qr, qm, qo, qc, s1, s2, s3, a, b, c, z, zw, t1w, t2w)
+    function computeAlpha(bytes32[24] calldata proof, uint256 xiSeed) internal pure returns (uint256) {
+        bytes32[16] memory data;
+        data[0] = bytes32(xiSeed);
+        for (uint256 i = 1; i < 16; i++) {
+            data[i] = proof[P_QL + i - 1];
+        }
+        return uint256(keccak256(abi.encodePacked(data))) % R;
+    }
+
+    /// Check the batched inverse provided in the proof and compute all the needed inverses.
+    /// The inverted values are in the order: zh, denH1, denH2, liS0, liS1, liS2 and the
+    /// Lagrange bases n * (xi - w^i).
+    function computeInverses(bytes32[24] calldata proof, Challenges memory ch)
+        internal
+        pure
+        returns (bool ok, Inverses memory inv)
+    {
+        uint256[] memory bases = inverseBases(ch);
+        uint256 nLagrange = bases.length - 21;
+        uint256[] memory acc = new uint256[](bases.length);
+        acc[0] = bases[0];
+        for (uint256 i = 1; i < bases.length; i++) {
+            acc[i] = mulmod(acc[i - 1], bases[i], R);
+        }
+        uint256 running = uint256(proof[P_INV]);
+        if (mulmod(acc[bases.length - 1], running, R) != 1) {
+            return (false, inv);
+        }
+        uint256[] memory inverses = new uint256[](bases.length);
+        for (uint256 i = bases.length - 1; i > 0; i--) {
+            inverses[i] = mulmod(running, acc[i - 1], R);
+            running = mulmod(running, bases[i], R);
+        }
+        inverses[0] = running;
+
+        inv.zhInv = inverses[0];
+        inv.denH1 = inverses[1];
+        inv.denH2 = inverses[2];
+        for (uint256 i = 0; i < 8; i++) {
+            inv.liS0[i] = inverses[3 + i];
+        }
+        for (uint256 i = 0; i < 4; i++) {
+            inv.liS1[i] = inverses[11 + i];
+        }
+        for (uint256 i = 0; i < 6; i++) {
+            inv.liS2[i] = inverses[15 + i];
+        }
+        // L_i(xi) = w^i * zh / (n * (xi - w^i))
+        inv.lagrange = new uint256[](nLagrange);
+        uint256 root = 1;
+        for (uint256 i = 0; i < nLagrange; i++) {
+            inv.lagrange[i] = mulmod(mulmod(inverses[21 + i], root, R), ch.zh, R);
+            root = mulmod(root, W1, R);
+        }
+        return (true, inv);
+    }
+
+    function inverseBases(Challenges memory ch) internal pure returns (uint256[] memory bases) {
+        bases = new uint256[](21 + (N_PUBLIC > 0 ? N_PUBLIC : 1));
+        bases[0] = ch.zh;
+        bases[1] = 1;
+        for (uint256 i = 0; i < 4; i++) {
+            bases[1] = mulmod(bases[1], sub(ch.y, ch.h1w4[i]), R);
+        }
+        bases[2] = 1;
+        for (uint256 i = 0; i < 3; i++) {
+            bases[2] = mulmod(bases[2], sub(ch.y, ch.h2w3[i]), R);
+            bases[2] = mulmod(bases[2], sub(ch.y, ch.h3w3[i]), R);
+        }
+        uint256 den = mulmod(pow(ch.h0w8[0], 6), 8, R);
+        for (uint256 i = 0; i < 8; i++) {
+            bases[3 + i] = mulmod(mulmod(den, ch.h0w8[(8 - i) % 8], R), sub(ch.y, ch.h0w8[i]), R);
+        }
+        den = mulmod(mulmod(ch.h1w4[0], ch.h1w4[0], R), 4, R);
+        for (uint256 i = 0; i < 4; i++) {
+            bases[11 + i] = mulmod(mulmod(den, ch.h1w4[(4 - i) % 4], R), sub(ch.y, ch.h1w4[i]), R);
+        }
+        uint256 xiW = mulmod(ch.xi, W1, R);
+        den = mulmod(mulmod(3, ch.h2w3[0], R), sub(ch.xi, xiW), R);
+        for (uint256 i = 0; i < 3; i++) {
+            bases[15 + i] = mulmod(mulmod(den, ch.h2w3[(3 - i) % 3], R), sub(ch.y, ch.h2w3[i]), R);
+        }
+        den = mulmod(mulmod(3, ch.h3w3[0], R), sub(xiW, ch.xi), R);
+        for (uint256 i = 0; i < 3; i++) {
+            bases[18 + i] = mulmod(mulmod(den, ch.h3w3[(3 - i) % 3], R), sub(ch.y, ch.h3w3[i]), R);
+        }
+        uint256 root = 1;
+        for (uint256 i = 21; i < bases.length; i++) {
+            bases[i] = mulmod(N, sub(ch.xi, root), R);
+            root = mulmod(root, W1, R);
+        }
+    }
+
+    /// PI(xi) = -sum_i(L_i(xi) * pub_i)
+    function computePi(uint256[] memory pubs, Inverses memory inv) internal pure returns (uint256 pi) {
+        for (uint256 i = 0; i < pubs.length; i++) {
+            pi = sub(pi, mulmod(inv.lagrange[i], pubs[i], R));
+        }
+    }
+
+    function computeR0(bytes32[24] calldata proof, Challenges memory ch, Inverses memory inv)
+        internal
+        pure
+        returns (uint256 r0)
+    {
+        uint256 base = sub(pow(ch.y, 8), ch.xi);
+        uint256[] memory coefficients = new uint256[](8);
+        coefficients[0] = uint256(proof[P_QL]);
+        coefficients[1] = uint256(proof[P_QR]);
+        coefficients[2] = uint256(proof[P_QO]);
+        coefficients[3] = uint256(proof[P_QM]);
+        coefficients[4] = uint256(proof[P_QC]);
+        coefficients[5] = uint256(proof[P_S1]);
+        coefficients[6] = uint256(proof[P_S2]);
+        coefficients[7] = uint256(proof[P_S3]);
+        for (uint256 i = 0; i < 8; i++) {
+            r0 = addmod(r0, evalTerm(coefficients, ch.h0w8[i], base, inv.liS0[i]), R);
+        }
+    }
+
+    function computeR1(bytes32[24] calldata proof, Challenges memory ch, Inverses memory inv, uint256 pi)
+        internal
+        pure
+        returns (uint256 r1)
+    {
+        uint256 base = sub(pow(ch.y, 4), ch.xi);
+        uint256 a = uint256(proof[P_A]);
+        uint256 b = uint256(proof[P_B]);
+        uint256 c = uint256(proof[P_C]);
+        uint256 t0 = mulmod(uint256(proof[P_QL]), a, R);
+        t0 = addmod(t0, mulmod(uint256(proof[P_QR]), b, R), R);
+        t0 = addmod(t0, mulmod(mulmod(uint256(proof[P_QM]), a, R), b, R), R);
+        t0 = addmod(t0, mulmod(uint256(proof[P_QO]), c, R), R);
+        t0 = addmod(t0, uint256(proof[P_QC]), R);
+        t0 = mulmod(addmod(t0, pi, R), inv.zhInv, R);
+        uint256[] memory coefficients = new uint256[](4);
+        coefficients[0] = a;
+        coefficients[1] = b;
+        coefficients[2] = c;
+        coefficients[3] = t0;
+        for (uint256 i = 0; i < 4; i++) {
+            r1 = addmod(r1, evalTerm(coefficients, ch.h1w4[i], base, inv.liS1[i]), R);
+        }
+    }
+
+    function computeR2(bytes32[24] calldata proof, Challenges memory ch, Inverses memory inv)
+        internal
+        pure
+        returns (uint256 r2)
+    {
+        uint256 base = sub(
+            pow(ch.y, 6), mulmod(mulmod(pow(ch.y, 3), ch.xi, R), addmod(1, W1, R), R)
+        );
+        base = addmod(base, mulmod(mulmod(ch.xi, ch.xi, R), W1, R), R);
+        uint256[] memory coefficients = new uint256[](3);
+        coefficients[0] = uint256(proof[P_Z]);
+        coefficients[1] = mulmod(mulmod(sub(uint256(proof[P_Z]), 1), inv.lagrange[0], R), inv.zhInv, R);
+        coefficients[2] = computeT2(proof, ch, inv);
+        for (uint256 i = 0; i < 3; i++) {
+            r2 = addmod(r2, evalTerm(coefficients, ch.h2w3[i], base, inv.liS2[i]), R);
+        }
+        coefficients[0] = uint256(proof[P_ZW]);
+        coefficients[1] = uint256(proof[P_T1W]);
+        coefficients[2] = uint256(proof[P_T2W]);
+        for (uint256 i = 0; i < 3; i++) {
+            r2 = addmod(r2, evalTerm(coefficients, ch.h3w3[i], base, inv.liS2[3 + i]), R);
+        }
+    }
+
+    function computeT2(bytes32[24] calldata proof, Challenges memory ch, Inverses memory inv)
+        internal
+        pure
+        returns (uint256)
+    {
+        uint256 betaXi = mulmod(ch.beta, ch.xi, R);
+        uint256 left = addmod(addmod(uint256(proof[P_A]), betaXi, R), ch.gamma, R);
+        left = mulmod(left, addmod(addmod(uint256(proof[P_B]), mulmod(betaXi, K1, R), R), ch.gamma, R), R);
+        left = mulmod(left, addmod(addmod(uint256(proof[P_C]), mulmod(betaXi, K2, R), R), ch.gamma, R), R);
+        left = mulmod(left, uint256(proof[P_Z]), R);
+        uint256 right = addmod(addmod(uint256(proof[P_A]), mulmod(ch.beta, uint256(proof[P_S1]), R), R), ch.gamma, R);
+        right = mulmod(
+            right, addmod(addmod(uint256(proof[P_B]), mulmod(ch.beta, uint256(proof[P_S2]), R), R), ch.gamma, R), R
+        );
+        right = mulmod(
+            right, addmod(addmod(uint256(proof[P_C]), mulmod(ch.beta, uint256(proof[P_S3]), R), R), ch.gamma, R), R
+        );
+        right = mulmod(right, uint256(proof[P_ZW]), R);
+        return mulmod(sub(left, right), inv.zhInv, R);
+    }
+
+    /// Evaluate sum_k(coefficients[k] * root^k) * base * inverse
+    function evalTerm(uint256[] memory coefficients, uint256 root, uint256 base, uint256 inverse)
+        internal
+        pure
+        returns (uint256)
+    {
+        uint256 h = 1;
+        uint256 value = 0;
+        for (uint256 k = 0; k < coefficients.length; k++) {
+            value = addmod(value, mulmod(coefficients[k], h, R), R);
+            h = mulmod(h, root, R);
+        }
+        return mulmod(mulmod(value, base, R), inverse, R);
+    }
+
+    /// Check e(F - E - J + y·W2, G2) · e(-W2, X2) == 1
+    function checkPairing(
+        bytes32[24] calldata proof,
+        Challenges memory ch,
+        Inverses memory inv,
+        uint256 r0,
+        uint256 r1,
+        uint256 r2
+    ) internal view returns (bool) {
+        (bool ok, uint256[2] memory lhs) = computePairingLhs(proof, ch, pairingScalars(ch, inv, r0, r1, r2));
+        if (!ok) {
+            return false;
+        }
+        uint256[2] memory w2 = g1Neg(proofPoint(proof, P_W2));
+        // EIP-197 encodes the G2 points coordinates with the imaginary part first
+        uint256[12] memory input;
+        input[0] = lhs[0];
+        input[1] = lhs[1];
+        input[2] = G2X2;
+        input[3] = G2X1;
+        input[4] = G2Y2;
+        input[5] = G2Y1;
+        input[6] = w2[0];
+        input[7] = w2[1];
+        input[8] = X2X2;
+        input[9] = X2X1;
+        input[10] = X2Y2;
+        input[11] = X2Y1;
+        uint256[1] memory output;
+        assembly {
+            ok := staticcall(gas(), 0x08, input, 0x180, output, 0x20)
+        }
+        return ok && output[0] == 1;
+    }
+
+    /// The scalars of F = C1·quotient1 + C2·quotient2 + C0, E = G1·e and J = W1·numerator
+    /// in the order: quotient1, quotient2, e, numerator.
+    function pairingScalars(Challenges memory ch, Inverses memory inv, uint256 r0, uint256 r1, uint256 r2)
+        internal
+        pure
+        returns (uint256[4] memory scalars)
+    {
+        uint256 numerator = 1;
+        for (uint256 i = 0; i < 8; i++) {
+            numerator = mulmod(numerator, sub(ch.y, ch.h0w8[i]), R);
+        }
+        scalars[0] = mulmod(mulmod(ch.alpha, numerator, R), inv.denH1, R);
+        scalars[1] = mulmod(mulmod(mulmod(ch.alpha, ch.alpha, R), numerator, R), inv.denH2, R);
+        scalars[2] = addmod(addmod(r0, mulmod(scalars[0], r1, R), R), mulmod(scalars[1], r2, R), R);
+        scalars[3] = numerator;
+    }
+
+    /// Compute F - E - J + y·W2
+    function computePairingLhs(bytes32[24] calldata proof, Challenges memory ch, uint256[4] memory scalars)
+        internal
+        view
+        returns (bool ok, uint256[2] memory lhs)
+    {
+        uint256[2] memory point;
+        (ok, lhs) = g1Mul(proofPoint(proof, P_C1), scalars[0]);
+        if (!ok) return (false, lhs);
+        (ok, point) = g1Mul(proofPoint(proof, P_C2), scalars[1]);
+        if (!ok) return (false, lhs);
+        (ok, lhs) = g1Add(lhs, point);
+        if (!ok) return (false, lhs);
+        (ok, lhs) = g1Add(lhs, [C0X, C0Y]);
+        if (!ok) return (false, lhs);
+        (ok, point) = g1Mul([G1X, G1Y], scalars[2]);
+        if (!ok) return (false, lhs);
+        (ok, lhs) = g1Add(lhs, g1Neg(point));
+        if (!ok) return (false, lhs);
+        (ok, point) = g1Mul(proofPoint(proof, P_W1), scalars[3]);
+        if (!ok) return (false, lhs);
+        (ok, lhs) = g1Add(lhs, g1Neg(point));
+        if (!ok) return (false, lhs);
+        (ok, point) = g1Mul(proofPoint(proof, P_W2), ch.y);
+        if (!ok) return (false, lhs);
+        (ok, lhs) = g1Add(lhs, point);
+    }
+
+    function proofPoint(bytes32[24] calldata proof, uint256 pos) internal pure returns (uint256[2] memory) {
+        return [uint256(proof[pos]), uint256(proof[pos + 1])];
+    }
+
+    function g1Add(uint256[2] memory p1, uint256[2] memory p2) internal view returns (bool ok, uint256[2] memory r) {
+        uint256[4] memory input = [p1[0], p1[1], p2[0], p2[1]];
+        assembly {
+            ok := staticcall(gas(), 0x06, input, 0x80, r, 0x40)
+        }
+    }
+
+    function g1Mul(uint256[2] memory p, uint256 s) internal view returns (bool ok, uint256[2] memory r) {
+        uint256[3] memory input = [p[0], p[1], s];
+        assembly {
+            ok := staticcall(gas(), 0x07, input, 0x60, r, 0x40)
+        }
+    }
+
+    function g1Neg(uint256[2] memory p) internal pure returns (uint256[2] memory) {
+        return [p[0], (Q - p[1]) % Q];
+    }
+
+    function sub(uint256 a, uint256 b) internal pure returns (uint256) {
+        return addmod(a, R - b, R);
+    }
+
+    function pow(uint256 base, uint256 exp) internal pure returns (uint256 result) {
+        result = 1;
+        for (uint256 i = 0; i < exp; i++) {
+            result = mulmod(result, base, R);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    check_paring,
+    curve::{Bn254, Curve, Point as _},
+    Keccak256Transcript, PreparedVerificationKey, Proof, Public, VerificationKey, VerifyError,
+};
+
+/// All the intermediate values computed by the verifier before the pairing check, with the
+/// same names used by the snarkjs' `verifier` debug logs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct VerificationTrace<C: Curve = Bn254> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub beta: C::Fr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub gamma: C::Fr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub xi: C::Fr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub alpha: C::Fr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub y: C::Fr,
+    /// The vanishing polynomial evaluation `xi^n - 1`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub zh: C::Fr,
+    /// The first Lagrange polynomial evaluation `L1(xi)`
+    #[cfg_attr(feature = "serde", serde(rename = "L1", with = "crate::serde::fr"))]
+    pub l1: C::Fr,
+    /// The public inputs polynomial evaluation `PI(xi)`
+    #[cfg_attr(feature = "serde", serde(rename = "PI", with = "crate::serde::fr"))]
+    pub pi: C::Fr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub r0: C::Fr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub r1: C::Fr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub r2: C::Fr,
+    #[cfg_attr(feature = "serde", serde(rename = "F", with = "crate::serde::g1"))]
+    pub f: C::G1,
+    #[cfg_attr(feature = "serde", serde(rename = "E", with = "crate::serde::g1"))]
+    pub e: C::G1,
+    #[cfg_attr(feature = "serde", serde(rename = "J", with = "crate::serde::g1"))]
+    pub j: C::G1,
+}
+
+/// Like [`crate::verify`] but return also the [`VerificationTrace`] with the intermediate
+/// values (`F`, `E` and `J` in affine coordinates). The trace is `None` only if the
+/// verification fails before computing them: on a malformed key, invalid public inputs,
+/// commitments or challenges, or on a wrong provided inverse.
+pub fn verify_with_trace<C: Curve>(
+    vk: &VerificationKey<C>,
+    proof: &Proof<C>,
+    pubs: &Public,
+) -> (Result<(), VerifyError>, Option<VerificationTrace<C>>) {
+    let vk = PreparedVerificationKey::from(vk);
+    let (challenges, mut trace) = match proof.compute_trace::<Keccak256Transcript<C>>(&vk, pubs) {
+        Ok(computed) => computed,
+        Err(error) => return (Err(error), None),
+    };
+    let lhs = proof.compute_pairing_lhs(&challenges, trace.f, trace.e, trace.j);
+    for point in [&mut trace.f, &mut trace.e, &mut trace.j] {
+        point.normalize();
+    }
+    (check_paring(lhs, proof.polynomials.w2, &vk), Some(trace))
+}
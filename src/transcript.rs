@@ -0,0 +1,156 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+use core::marker::PhantomData;
+use digest::Digest as _;
+
+use crate::curve::{Bn254, Curve, Field, Point};
+
+#[cfg(feature = "poseidon")]
+mod poseidon;
+
+#[cfg(feature = "poseidon")]
+pub use poseidon::{poseidon, PoseidonTranscript, POSEIDON_MAX_INPUTS};
+
+/// The Fiat-Shamir transcript used to derive the verifier challenges: as in snarkjs,
+/// every challenge is squeezed from the data absorbed after the previous one, and the
+/// verifier absorbs the previous challenge as first element of the next round.
+pub trait Transcript: Default {
+    /// The curve of the absorbed values and of the challenges.
+    type Curve: Curve;
+
+    /// Absorb a scalar field element.
+    fn absorb_scalar(&mut self, scalar: Fr<Self>);
+
+    /// Absorb a G1 point by its affine coordinates (the point at infinity is `(0, 0)`).
+    fn absorb_g1(&mut self, point: G1<Self>);
+
+    /// Squeeze a challenge from the absorbed data and reset the transcript.
+    fn squeeze_challenge(&mut self) -> Fr<Self>;
+}
+
+type Fr<T> = <<T as Transcript>::Curve as Curve>::Fr;
+type G1<T> = <<T as Transcript>::Curve as Curve>::G1;
+
+/// The snarkjs' Keccak256 transcript: the challenge is the Keccak256 hash of the absorbed
+/// values as big-endian words (32 bytes for the scalars and the curve's base field size for
+/// the coordinates), reduced in the scalar field.
+#[derive(Clone)]
+pub struct Keccak256Transcript<C: Curve = Bn254>(sha3::Keccak256, PhantomData<C>);
+
+impl<C: Curve> Default for Keccak256Transcript<C> {
+    fn default() -> Self {
+        Self(Default::default(), PhantomData)
+    }
+}
+
+impl<C: Curve> Transcript for Keccak256Transcript<C> {
+    type Curve = C;
+
+    fn absorb_scalar(&mut self, scalar: C::Fr) {
+        self.0.update(scalar.to_be_bytes());
+    }
+
+    fn absorb_g1(&mut self, point: C::G1) {
+        let mut encoded = alloc::vec![0_u8; <C::G1 as Point>::ENCODED_LEN];
+        point.encode(&mut encoded);
+        self.0.update(encoded);
+    }
+
+    fn squeeze_challenge(&mut self) -> C::Fr {
+        C::Fr::from_be_bytes_reduced(&self.0.finalize_reset().into())
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use substrate_bn::{arith::U256, AffineG1, Fr, Group, G1};
+
+    use super::*;
+    use crate::{
+        hash::Hasher as _,
+        macros::fr,
+        utils::{IntoFq as _, IntoFr as _},
+    };
+
+    #[test]
+    fn squeeze_the_hash_of_the_absorbed_big_endian_words() {
+        let mut transcript = Keccak256Transcript::<Bn254>::default();
+        transcript.absorb_scalar(Fr::zero());
+        transcript.absorb_scalar(Fr::zero());
+
+        assert_eq!(
+            fr!("1c053d5dd362f3501993d420ba93e87eb29b2bb845ddeefe74b26929c7ba5fb2"),
+            transcript.squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn absorb_the_canonical_scalar_representation() {
+        let mut transcript = Keccak256Transcript::<Bn254>::default();
+        transcript.absorb_scalar(Fr::one());
+
+        assert_eq!(
+            [U256::one()].as_slice().hash().into_fr(),
+            transcript.squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn reset_the_state_when_squeeze() {
+        let mut transcript = Keccak256Transcript::<Bn254>::default();
+        transcript.absorb_scalar(3_u64.into_fr());
+        transcript.squeeze_challenge();
+
+        transcript.absorb_scalar(Fr::zero());
+
+        assert_eq!(
+            fr!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"),
+            transcript.squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn absorb_the_affine_coordinates_of_a_point() {
+        let z = 7_u64.into_fq();
+        let point = G1::one() * 5_u64.into_fr();
+        let affine = AffineG1::from_jacobian(point).unwrap();
+        let projective = G1::new(affine.x() * z * z, affine.y() * z * z * z, z);
+        let mut transcript = Keccak256Transcript::<Bn254>::default();
+
+        transcript.absorb_g1(projective);
+
+        assert_eq!(
+            [affine.x().into_u256(), affine.y().into_u256()]
+                .as_slice()
+                .hash()
+                .into_fr(),
+            transcript.squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn absorb_the_point_at_infinity_as_zeros() {
+        let mut transcript = Keccak256Transcript::<Bn254>::default();
+
+        transcript.absorb_g1(<G1 as Group>::zero());
+
+        assert_eq!(
+            [U256::zero(), U256::zero()].as_slice().hash().into_fr(),
+            transcript.squeeze_challenge()
+        );
+    }
+}
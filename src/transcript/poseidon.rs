@@ -0,0 +1,272 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The circomlib Poseidon hash over the BN254 scalar field (`x^5` S-box, 8 full rounds and
+//! the circomlib partial rounds) and the transcript built on it.
+
+use alloc::vec::Vec;
+use substrate_bn::{arith::U256, AffineG1, Fq, Fr, G1};
+
+use super::Transcript;
+use crate::{curve::Bn254, utils::IntoFr as _};
+
+mod constants;
+
+use constants::{constants, PARTIAL_ROUNDS};
+
+/// The maximum number of inputs hashed by a single Poseidon permutation (`t = 17`).
+pub const POSEIDON_MAX_INPUTS: usize = 16;
+
+const FULL_ROUNDS: usize = 8;
+
+/// The circomlib `poseidon(inputs)` hash: the state is the `0` domain tag followed by the
+/// inputs and the output is the first element of the permuted state.
+///
+/// # Panics
+///
+/// If `inputs` is empty or longer than [`POSEIDON_MAX_INPUTS`].
+pub fn poseidon(inputs: &[Fr]) -> Fr {
+    assert!(
+        (1..=POSEIDON_MAX_INPUTS).contains(&inputs.len()),
+        "Poseidon takes from 1 to {POSEIDON_MAX_INPUTS} inputs: got {}",
+        inputs.len()
+    );
+    let t = inputs.len() + 1;
+    let (ark, mds) = constants(t);
+    let partial_rounds = PARTIAL_ROUNDS[t - 2];
+    let mds = mds.iter().map(|&v| v.into_fr()).collect::<Vec<_>>();
+    let mut state = core::iter::once(Fr::zero())
+        .chain(inputs.iter().copied())
+        .collect::<Vec<_>>();
+
+    for (round, ark) in ark.chunks(t).enumerate() {
+        for (s, &c) in state.iter_mut().zip(ark) {
+            *s = *s + c.into_fr();
+        }
+        let full = round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + partial_rounds;
+        let sbox_len = if full { t } else { 1 };
+        for s in &mut state[..sbox_len] {
+            *s = sbox(*s);
+        }
+        state = mds
+            .chunks(t)
+            .map(|row| {
+                row.iter()
+                    .zip(&state)
+                    .fold(Fr::zero(), |acc, (&m, &s)| acc + m * s)
+            })
+            .collect();
+    }
+    state[0]
+}
+
+fn sbox(x: Fr) -> Fr {
+    let x2 = x * x;
+    x2 * x2 * x
+}
+
+/// The Poseidon transcript for proofs verified inside circom circuits: the challenge is
+/// `poseidon` of the absorbed values. The G1 coordinates are absorbed as two elements each,
+/// the low and the high 128 bits, because the base field doesn't fit in the scalar one. More
+/// than [`POSEIDON_MAX_INPUTS`] values are chained: the first ones are hashed and every next
+/// hash takes the previous one followed by up to `POSEIDON_MAX_INPUTS - 1` new values.
+/// Squeezing an empty transcript hashes a single `0`.
+#[derive(Clone, Default)]
+pub struct PoseidonTranscript(Vec<Fr>);
+
+impl Transcript for PoseidonTranscript {
+    type Curve = Bn254;
+
+    fn absorb_scalar(&mut self, scalar: Fr) {
+        self.0.push(scalar);
+    }
+
+    fn absorb_g1(&mut self, point: G1) {
+        let (x, y) = AffineG1::from_jacobian(point)
+            .map(|p| (p.x(), p.y()))
+            .unwrap_or((Fq::zero(), Fq::zero()));
+        for coordinate in [x, y] {
+            let U256([low, high]) = coordinate.into_u256();
+            self.0.push(U256([low, 0]).into_fr());
+            self.0.push(U256([high, 0]).into_fr());
+        }
+    }
+
+    fn squeeze_challenge(&mut self) -> Fr {
+        let inputs = core::mem::take(&mut self.0);
+        if inputs.is_empty() {
+            return poseidon(&[Fr::zero()]);
+        }
+        let (first, others) = inputs.split_at(inputs.len().min(POSEIDON_MAX_INPUTS));
+        others
+            .chunks(POSEIDON_MAX_INPUTS - 1)
+            .fold(poseidon(first), |hash, chunk| {
+                let mut inputs = Vec::with_capacity(chunk.len() + 1);
+                inputs.push(hash);
+                inputs.extend_from_slice(chunk);
+                poseidon(&inputs)
+            })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use rstest::rstest;
+    use substrate_bn::Group as _;
+
+    use super::*;
+    use crate::{macros::fr, utils::IntoFq as _};
+
+    fn ones(n: usize) -> Vec<Fr> {
+        core::iter::repeat(Fr::one()).take(n).collect()
+    }
+
+    fn range(values: core::ops::Range<u64>) -> Vec<Fr> {
+        values.map(|v| v.into_fr()).collect()
+    }
+
+    // The circomlibjs `poseidon([1, ..., 1])` outputs.
+    #[rstest]
+    #[case(1, fr!("29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133"))]
+    #[case(2, fr!("007af346e2d304279e79e0a9f3023f771294a78acb70e73f90afe27cad401e81"))]
+    #[case(3, fr!("02c0066e10a72abd2b33c3b214cb3e81bcb1b6e30961cd23c202b18673bf2543"))]
+    #[case(4, fr!("082c9c370a0d24f4416fbc414a37681f78442d27d86385991c17d6fc0c4b7d71"))]
+    #[case(5, fr!("10389605ae688d4f14db853122c47d66a803c72b41589cb1bf868741b206b9bb"))]
+    #[case(6, fr!("2a73f679328c3eab724aa3e5bdbf50b39035d7729f135b9709890f85c5dc5e76"))]
+    #[case(7, fr!("2276310aa7f3343a284214139d9da959be2a31b2c708a5f81954b265e53a30b8"))]
+    #[case(8, fr!("177e1453c446e1b07d2b4233425147095c4fcabb233d230b6d46a214d95b2884"))]
+    #[case(9, fr!("0e8fee2fe49da30fdeeb48c42ebb44cc6ee7055f61fbca5e313b8a5fca834c47"))]
+    #[case(10, fr!("2ec4c65e6378ab8c7330854f4a7077c1ff9260e44885c4b81dd131ad3a86cd96"))]
+    #[case(11, fr!("00713d41eca635f117d4ecbceb5f3a66dc4142eb70b56765bc358f1bec40bb9b"))]
+    #[case(12, fr!("14390be0baef249bd47c65ddac65c2e52e8513c081c1cd72c98006098e9a8fbe"))]
+    fn hash_as_circomlib(#[case] n: usize, #[case] expected: Fr) {
+        assert_eq!(expected, poseidon(&ones(n)));
+    }
+
+    #[test]
+    fn hash_two_different_inputs_as_circomlib() {
+        assert_eq!(
+            fr!("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"),
+            poseidon(&range(1..3))
+        );
+    }
+
+    // Self-computed regression values: the outputs of this implementation, not checked
+    // against circomlibjs.
+    #[rstest]
+    #[case(13, fr!("2ebd80a1a986553e4357f346d3e191fe9406fdf34722781fe8536f63e6c65c6c"))]
+    #[case(14, fr!("262ac4491cd2d08195364ff7a50cee3842753271bc7232d85593963a7f68e9dd"))]
+    #[case(15, fr!("1be1d1afed0b5a818bda051514314123da16639a98f38a88242701959ec7cd3d"))]
+    #[case(16, fr!("23eb8f6fd942dcd0af0a13e86fb53cfc79b1940dd2b59a9b8d0855762b7ed38e"))]
+    fn hash_up_to_16_inputs(#[case] n: usize, #[case] expected: Fr) {
+        assert_eq!(expected, poseidon(&ones(n)));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "Poseidon takes from 1 to 16 inputs")]
+    fn reject_unsupported_inputs_number(#[values(0, 17)] n: usize) {
+        poseidon(&ones(n));
+    }
+
+    #[test]
+    fn squeeze_the_hash_of_the_absorbed_scalars_and_reset() {
+        let mut transcript = PoseidonTranscript::default();
+        transcript.absorb_scalar(Fr::one());
+        transcript.absorb_scalar(2_u64.into_fr());
+
+        assert_eq!(poseidon(&range(1..3)), transcript.squeeze_challenge());
+
+        transcript.absorb_scalar(Fr::one());
+        assert_eq!(poseidon(&ones(1)), transcript.squeeze_challenge());
+    }
+
+    #[rstest]
+    #[case::two_chunks(20)]
+    #[case::full_second_chunk(31)]
+    fn chain_the_hashes_of_more_than_16_inputs(#[case] n: u64) {
+        let mut transcript = PoseidonTranscript::default();
+        for v in range(0..n) {
+            transcript.absorb_scalar(v);
+        }
+        let mut second = range(16..n);
+        second.insert(0, poseidon(&range(0..16)));
+
+        assert_eq!(poseidon(&second), transcript.squeeze_challenge());
+    }
+
+    #[test]
+    fn chain_three_hashes() {
+        let mut transcript = PoseidonTranscript::default();
+        for v in range(0..32) {
+            transcript.absorb_scalar(v);
+        }
+        let mut second = range(16..31);
+        second.insert(0, poseidon(&range(0..16)));
+
+        assert_eq!(
+            poseidon(&[poseidon(&second), 31_u64.into_fr()]),
+            transcript.squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn squeeze_the_hash_of_zero_when_empty() {
+        assert_eq!(
+            poseidon(&[Fr::zero()]),
+            PoseidonTranscript::default().squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn absorb_the_affine_coordinates_as_128_bits_limbs() {
+        let z = 7_u64.into_fq();
+        let affine = AffineG1::from_jacobian(G1::one() * 5_u64.into_fr()).unwrap();
+        let projective = G1::new(affine.x() * z * z, affine.y() * z * z * z, z);
+        let limbs = |v: Fq| {
+            let U256([low, high]) = v.into_u256();
+            [U256([low, 0]).into_fr(), U256([high, 0]).into_fr()]
+        };
+        let [x_low, x_high] = limbs(affine.x());
+        let [y_low, y_high] = limbs(affine.y());
+        let mut transcript = PoseidonTranscript::default();
+
+        transcript.absorb_g1(projective);
+
+        assert_eq!(
+            poseidon(&[x_low, x_high, y_low, y_high]),
+            transcript.squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn absorb_the_limbs_in_low_high_order() {
+        let mut transcript = PoseidonTranscript::default();
+        transcript.absorb_g1(G1::one());
+
+        assert_eq!(
+            poseidon(&[Fr::one(), Fr::zero(), 2_u64.into_fr(), Fr::zero()]),
+            transcript.squeeze_challenge()
+        );
+    }
+
+    #[test]
+    fn absorb_the_point_at_infinity_as_zeros() {
+        let mut transcript = PoseidonTranscript::default();
+        transcript.absorb_g1(G1::zero());
+
+        assert_eq!(poseidon(&[Fr::zero(); 4]), transcript.squeeze_challenge());
+    }
+}
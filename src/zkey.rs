@@ -0,0 +1,327 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Read the verification key from the snarkjs' `.zkey` binary format. The file is
+//! composed by a `zkey` magic, a version and a list of sections: we just need the
+//! first two (the protocol header and the fflonk header) to rebuild the verification key.
+//! All the field elements are stored as little-endian values in Montgomery form and
+//! the points in affine coordinates, where the point at infinity is all zeros.
+
+#![cfg(feature = "zkey")]
+
+use alloc::vec::Vec;
+use snafu::Snafu;
+use substrate_bn::{arith::U256, AffineG1, AffineG2, Fq, Fq2, Fr, Group, G1, G2};
+
+use crate::{
+    curve::FR_MODULUS, macros::u256, utils::IntoFr, Bn254, Curve, GroupError, VerificationKey,
+    VerificationKeyValidationError,
+};
+
+const MAGIC: &[u8; 4] = b"zkey";
+const HEADER_SECTION: u32 = 1;
+const FFLONK_HEADER_SECTION: u32 = 2;
+const FFLONK_PROTOCOL_ID: u32 = 10;
+
+/// Zkey decoding error
+#[derive(Snafu, Debug)]
+pub enum ZkeyError {
+    /// The data don't start with the zkey magic
+    #[snafu(display("Invalid zkey magic: not a zkey file"))]
+    InvalidMagic,
+    /// The data end before the expected field
+    #[snafu(display("Truncated zkey data: cannot read '{field}'"))]
+    Truncated { field: &'static str },
+    /// A needed section is not present
+    #[snafu(display("Missing zkey section {id}"))]
+    MissingSection { id: u32 },
+    /// The zkey is not for the fflonk protocol
+    #[snafu(display("Unsupported protocol id {id}: expected fflonk ({FFLONK_PROTOCOL_ID})"))]
+    UnsupportedProtocol { id: u32 },
+    /// The zkey is not for the bn128 curve
+    #[snafu(display("Unsupported curve: only bn128 is supported"))]
+    UnsupportedCurve,
+    /// The domain size is not a valid power of two
+    #[snafu(display("Invalid domain size {domain_size}"))]
+    InvalidDomainSize { domain_size: u32 },
+    /// The value is not a field element
+    #[snafu(display("Invalid field zkey data '{field}'"))]
+    InvalidField { field: &'static str },
+    /// The value is not a valid curve point
+    #[snafu(display("Invalid point zkey data '{field}': {error:?}"))]
+    InvalidGroup {
+        field: &'static str,
+        error: GroupError,
+    },
+    /// The key is not self-consistent
+    #[snafu(display("Invalid verification key: {source}"))]
+    InvalidKey {
+        source: VerificationKeyValidationError,
+    },
+}
+
+impl VerificationKey {
+    /// Read the verification key from the content of a snarkjs' fflonk `.zkey` file: as
+    /// the json keys, it should pass [`VerificationKey::validate`].
+    pub fn from_zkey(data: &[u8]) -> Result<Self, ZkeyError> {
+        let sections = read_sections(data)?;
+        let section = |id| {
+            sections
+                .iter()
+                .find(|(section_id, _)| *section_id == id)
+                .map(|(_, data)| *data)
+                .ok_or(ZkeyError::MissingSection { id })
+        };
+
+        let protocol = Reader::new(section(HEADER_SECTION)?).u32("protocol")?;
+        if protocol != FFLONK_PROTOCOL_ID {
+            return Err(ZkeyError::UnsupportedProtocol { id: protocol });
+        }
+        let vk = read_fflonk_header(&mut Reader::new(section(FFLONK_HEADER_SECTION)?))?;
+        vk.validate()
+            .map_err(|source| ZkeyError::InvalidKey { source })?;
+        Ok(vk)
+    }
+}
+
+fn read_sections(data: &[u8]) -> Result<Vec<(u32, &[u8])>, ZkeyError> {
+    let mut reader = Reader::new(data);
+    if reader.take(MAGIC.len(), "magic")? != MAGIC {
+        return Err(ZkeyError::InvalidMagic);
+    }
+    let _version = reader.u32("version")?;
+    let n_sections = reader.u32("sections number")?;
+    (0..n_sections)
+        .map(|_| {
+            let id = reader.u32("section id")?;
+            let size = reader.u64("section size")?;
+            let size = usize::try_from(size).map_err(|_| ZkeyError::Truncated {
+                field: "section data",
+            })?;
+            reader.take(size, "section data").map(|data| (id, data))
+        })
+        .collect()
+}
+
+fn read_fflonk_header(reader: &mut Reader) -> Result<VerificationKey, ZkeyError> {
+    let n8q = reader.u32("n8q")?;
+    let q = reader.take(n8q as usize, "q")?;
+    let n8r = reader.u32("n8r")?;
+    let r = reader.take(n8r as usize, "r")?;
+    if n8q != 32 || n8r != 32 || le_u256(q) != Fq::modulus() || le_u256(r) != FR_MODULUS {
+        return Err(ZkeyError::UnsupportedCurve);
+    }
+    let _n_vars = reader.u32("nVars")?;
+    let n_public = reader.u32("nPublic")?;
+    let domain_size = reader.u32("domainSize")?;
+    let power = domain_size.trailing_zeros();
+    if !domain_size.is_power_of_two() || power > Bn254::MAX_POWER as u32 {
+        return Err(ZkeyError::InvalidDomainSize { domain_size });
+    }
+    let _n_additions = reader.u32("nAdditions")?;
+    let _n_constraints = reader.u32("nConstraints")?;
+
+    Ok(VerificationKey {
+        n_public: n_public as usize,
+        power: power as u8,
+        k1: reader.fr("k1")?,
+        k2: reader.fr("k2")?,
+        w: root_of_unity(power),
+        w3: reader.fr("w3")?,
+        w4: reader.fr("w4")?,
+        w8: reader.fr("w8")?,
+        wr: reader.fr("wr")?,
+        x2: reader.g2("X_2")?,
+        c0: reader.g1("C0")?,
+    })
+}
+
+/// The primitive `2^power`-th root of unity used by snarkjs: `5^((r - 1) / 2^28)` squared
+/// `28 - power` times.
+fn root_of_unity(power: u32) -> Fr {
+    let t = u256!("000000030644e72e131a029b85045b68181585d2833e84879b9709143e1f593f");
+    let mut w = 5_u64.into_fr().pow(t.into_fr());
+    for _ in power..Bn254::MAX_POWER as u32 {
+        w = w * w;
+    }
+    w
+}
+
+fn le_u256(data: &[u8]) -> U256 {
+    let mut be = [0_u8; 32];
+    be.iter_mut()
+        .zip(data.iter().rev())
+        .for_each(|(dst, src)| *dst = *src);
+    U256::from_slice(&be).expect("Cannot fail: is a 32-byte array")
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    fr_r_inv: Fr,
+    fq_r_inv: Fq,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        // Montgomery factor R = 2^256: we need its inverse to convert the stored values
+        let fr_r = 2_u64.into_fr().pow(256_u64.into_fr());
+        let fq_r = Fq::from_u256(2.into())
+            .expect("2 is a member of Fq")
+            .pow(Fq::from_u256(256.into()).expect("256 is a member of Fq"));
+        Self {
+            data,
+            fr_r_inv: fr_r.inverse().expect("R is not zero"),
+            fq_r_inv: fq_r.inverse().expect("R is not zero"),
+        }
+    }
+
+    fn take(&mut self, len: usize, field: &'static str) -> Result<&'a [u8], ZkeyError> {
+        if self.data.len() < len {
+            return Err(ZkeyError::Truncated { field });
+        }
+        let (out, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(out)
+    }
+
+    fn u32(&mut self, field: &'static str) -> Result<u32, ZkeyError> {
+        self.take(4, field)
+            .map(|data| u32::from_le_bytes(data.try_into().expect("Cannot fail: 4 bytes")))
+    }
+
+    fn u64(&mut self, field: &'static str) -> Result<u64, ZkeyError> {
+        self.take(8, field)
+            .map(|data| u64::from_le_bytes(data.try_into().expect("Cannot fail: 8 bytes")))
+    }
+
+    fn fr(&mut self, field: &'static str) -> Result<Fr, ZkeyError> {
+        let value = le_u256(self.take(32, field)?);
+        Fr::new(value)
+            .map(|mont| mont * self.fr_r_inv)
+            .ok_or(ZkeyError::InvalidField { field })
+    }
+
+    fn fq(&mut self, field: &'static str) -> Result<Fq, ZkeyError> {
+        let value = le_u256(self.take(32, field)?);
+        Fq::from_u256(value)
+            .map(|mont| mont * self.fq_r_inv)
+            .map_err(|_| ZkeyError::InvalidField { field })
+    }
+
+    fn g1(&mut self, field: &'static str) -> Result<G1, ZkeyError> {
+        let x = self.fq(field)?;
+        let y = self.fq(field)?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(G1::zero());
+        }
+        AffineG1::new(x, y)
+            .map(Into::into)
+            .map_err(|error| ZkeyError::InvalidGroup {
+                field,
+                error: error.into(),
+            })
+    }
+
+    fn g2(&mut self, field: &'static str) -> Result<G2, ZkeyError> {
+        let x = Fq2::new(self.fq(field)?, self.fq(field)?);
+        let y = Fq2::new(self.fq(field)?, self.fq(field)?);
+        if x.is_zero() && y.is_zero() {
+            return Ok(G2::zero());
+        }
+        AffineG2::new(x, y)
+            .map(Into::into)
+            .map_err(|error| ZkeyError::InvalidGroup {
+                field,
+                error: error.into(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use rstest::rstest;
+
+    use super::*;
+
+    // A synthetic zkey, not a snarkjs' one: just the protocol and the fflonk header sections
+    // (1 and 2) that encode the `verification_key.json` key.
+    const ZKEY: &[u8] = include_bytes!("../resources/bins/verification_key.zkey");
+    // Offset of the fflonk header section data: magic, version, sections number and
+    // protocol section (id, size and data) followed by the header section id and size.
+    const FFLONK_HEADER_OFFSET: usize = 4 + 4 + 4 + (4 + 8 + 4) + (4 + 8);
+
+    #[test]
+    fn read_the_verification_key() {
+        let vk = VerificationKey::from_zkey(ZKEY).unwrap();
+
+        assert_eq!(1, vk.n_public);
+        assert_eq!(11, vk.power);
+        assert_eq!(2_u64.into_fr(), vk.k1);
+        assert_eq!(3_u64.into_fr(), vk.k2);
+        assert_eq!(
+            u256!("027a358499c5042bb4027fd7a5355d71b8c12c177494f0cad00a58f9769a2ee2").into_fr(),
+            vk.w
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn read_the_same_verification_key_of_the_json_fixture() {
+        let expected: VerificationKey =
+            serde_json::from_str(include_str!("../resources/bins/verification_key.json")).unwrap();
+
+        assert_eq!(expected, VerificationKey::from_zkey(ZKEY).unwrap());
+    }
+
+    #[rstest]
+    #[case::default_key(24, u256!("0c9fabc7845d50d2852e2a0371c6441f145e0db82e8326961c25f1e3e32b045b"))]
+    #[case::max_power(28, u256!("2a3c09f0a58a7e8500e0a7eb8ef62abc402d111e41112ed49bd61b6e725b19f0"))]
+    #[case::trivial(0, U256::one())]
+    fn compute_the_snarkjs_root_of_unity(#[case] power: u32, #[case] expected: U256) {
+        assert_eq!(expected.into_fr(), root_of_unity(power));
+    }
+
+    fn with(change: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+        let mut data = ZKEY.to_vec();
+        change(&mut data);
+        data
+    }
+
+    /// The expected error debug representation fragment
+    struct Expected(&'static str);
+
+    #[rstest]
+    #[case::empty(Vec::new(), Expected("Truncated"))]
+    #[case::invalid_magic(with(|d| d[0] = b'Z'), Expected("InvalidMagic"))]
+    #[case::truncated(with(|d| d.truncate(d.len() - 1)), Expected("Truncated"))]
+    #[case::missing_header(with(|d| d[8] = 1), Expected("MissingSection { id: 2 }"))]
+    #[case::groth16(with(|d| d[24] = 1), Expected("UnsupportedProtocol { id: 1 }"))]
+    #[case::other_curve(with(|d| d[FFLONK_HEADER_OFFSET + 4] ^= 1), Expected("UnsupportedCurve"))]
+    #[case::invalid_domain_size(with(|d| d[FFLONK_HEADER_OFFSET + 80] = 3), Expected("InvalidDomainSize"))]
+    #[case::not_a_field_element(with(|d| d[FFLONK_HEADER_OFFSET + 92..FFLONK_HEADER_OFFSET + 124].fill(0xff)), Expected("\"k1\""))]
+    #[case::not_a_coset_separator(with(|d| d[FFLONK_HEADER_OFFSET + 92..FFLONK_HEADER_OFFSET + 124].fill(0)), Expected("InvalidKey { source: InvalidCosetSeparator { field: \"k1\" } }"))]
+    #[case::one_point_domain(with(|d| d[FFLONK_HEADER_OFFSET + 80..FFLONK_HEADER_OFFSET + 84].copy_from_slice(&1_u32.to_le_bytes())), Expected("InvalidKey { source: NotPrimitiveRoot { field: \"w\", order: 1 } }"))]
+    #[case::not_a_point(with(|d| { let c0 = d.len() - 64; d[c0] ^= 1 }), Expected("\"C0\", error: NotOnCurve"))]
+    fn reject_invalid_zkey(#[case] data: Vec<u8>, #[case] expected: Expected) {
+        let expected = expected.0;
+        let error = VerificationKey::from_zkey(&data).unwrap_err();
+
+        assert!(
+            alloc::format!("{error:?}").contains(expected),
+            "{error:?} doesn't contain {expected}"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+/*
+ * Copyright 2024, Horizen Labs, Inc.
+ *
+ * fflonk_verifier is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fflonk_verifier is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/* Exercise the C ABI through the generated header: the tests in `tests/ffi.rs` call it with
+ * a valid key, proof and public inputs. */
+
+#include <string.h>
+
+#include "fflonk_verifier.h"
+
+#define PROOF_LEN 768
+
+#define CHECK(condition)   \
+    do {                   \
+        if (!(condition))  \
+            return __LINE__; \
+    } while (0)
+
+/* Return 0 if all the checks pass or the line of the failed one. */
+int fflonk_harness(const uint8_t *vk, size_t vk_len, const uint8_t *proof, size_t proof_len,
+                   const uint8_t *pubs, size_t pubs_len) {
+    uint8_t tampered[PROOF_LEN];
+
+    CHECK(proof_len == PROOF_LEN);
+    CHECK(fflonk_verify(vk, vk_len, proof, proof_len, pubs, pubs_len) == FFLONK_OK);
+
+    CHECK(fflonk_verify(NULL, vk_len, proof, proof_len, pubs, pubs_len) ==
+          FFLONK_ERROR_NULL_POINTER);
+    CHECK(fflonk_verify(vk, vk_len - 1, proof, proof_len, pubs, pubs_len) ==
+          FFLONK_ERROR_INVALID_KEY_DATA);
+    CHECK(fflonk_verify(vk, vk_len, proof, proof_len - 1, pubs, pubs_len) ==
+          FFLONK_ERROR_PROOF_WRONG_LENGTH);
+    CHECK(fflonk_verify(vk, vk_len, proof, proof_len, pubs, pubs_len - 1) ==
+          FFLONK_ERROR_PUBLIC_INPUTS_WRONG_LENGTH);
+    CHECK(fflonk_verify(vk, vk_len, proof, proof_len, NULL, 0) ==
+          FFLONK_ERROR_INVALID_PUBLIC_INPUTS_NUMBER);
+
+    /* The first commitment (0, 1) is not on the curve */
+    memcpy(tampered, proof, PROOF_LEN);
+    memset(tampered, 0, 64);
+    tampered[63] = 1;
+    CHECK(fflonk_verify(vk, vk_len, tampered, PROOF_LEN, pubs, pubs_len) ==
+          FFLONK_ERROR_PROOF_INVALID_POINT);
+
+    /* The last evaluation (the inverse) is not a field element */
+    memcpy(tampered, proof, PROOF_LEN);
+    memset(tampered + PROOF_LEN - 32, 0xff, 32);
+    CHECK(fflonk_verify(vk, vk_len, tampered, PROOF_LEN, pubs, pubs_len) ==
+          FFLONK_ERROR_PROOF_INVALID_FIELD);
+
+    /* A wrong evaluation */
+    memcpy(tampered, proof, PROOF_LEN);
+    tampered[4 * 64 + 31] ^= 1;
+    CHECK(fflonk_verify(vk, vk_len, tampered, PROOF_LEN, pubs, pubs_len) ==
+          FFLONK_ERROR_INVALID_INVERSE);
+
+    CHECK(strcmp(fflonk_error_message(FFLONK_OK), "Valid proof") == 0);
+    CHECK(strcmp(fflonk_error_message(FFLONK_ERROR_NOT_PAIRING), "Cannot verify paring") == 0);
+    CHECK(strcmp(fflonk_error_message(1), "Unknown result code") == 0);
+
+    return 0;
+}
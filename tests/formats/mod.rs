@@ -20,6 +20,7 @@ pub enum Formats {
     Json,
     Bytes,
     HexString,
+    Calldata,
 }
 impl Formats {
     pub fn resource(&self) -> &'static str {
@@ -27,6 +28,7 @@ impl Formats {
             Formats::Json => "proof.json",
             Formats::Bytes => "proof.bin",
             Formats::HexString => "proof.hex",
+            Formats::Calldata => "proof.calldata",
         }
     }
 
@@ -35,6 +37,7 @@ impl Formats {
             Formats::Json => "json",
             Formats::Bytes => "bytes",
             Formats::HexString => "hex-string",
+            Formats::Calldata => "calldata",
         }
     }
 
@@ -45,7 +48,7 @@ impl Formats {
     #[allow(unused)]
     pub fn expected_path(&self) -> PathBuf {
         match self {
-            Formats::Json => PathBuf::from(format!("resources/bins/expected.json")),
+            Formats::Json => PathBuf::from("resources/bins/expected.json"),
             _ => self.resource_path(),
         }
     }
@@ -51,7 +51,7 @@ fn should_verify_valid_proof() {
     let proof = Proof::try_from(&data).unwrap();
     let pubs = hex!("0d69b94acdfaca5bacc248a60b35b925a2374644ce0c1205db68228c8921d9d9").into();
 
-    verify(&key.into(), &proof, &pubs).unwrap();
+    verify(&key, &proof, &pubs).unwrap();
 }
 
 #[cfg(feature = "serde")]
@@ -33,6 +33,11 @@ mod should {
     use crate::formats::Formats;
     use pretty_assertions::assert_eq;
 
+    const VALID_PUBS_HEX: &str =
+        "0x110d778eaf8b8ef7ac10f8ac239a14df0eb292a8d1b71340d527b26301a9ab08";
+    const VALID_PUBS_DEC: &str =
+        "7713112592372404476342535432037683616424591277138491596200192981572885523208";
+
     #[rstest]
     fn show_help(mut bin: Command) {
         let output = bin.arg("--help").output().unwrap();
@@ -45,7 +50,8 @@ mod should {
     #[rstest]
     fn convert(
         mut bin: Command,
-        #[values(Formats::Json, Formats::Bytes, Formats::HexString)] from_fmt: Formats,
+        #[values(Formats::Json, Formats::Bytes, Formats::HexString, Formats::Calldata)]
+        from_fmt: Formats,
         #[values(Formats::Json, Formats::Bytes, Formats::HexString)] to_fmt: Formats,
     ) {
         let tmpfile = tempfile::NamedTempFile::new().unwrap();
@@ -72,7 +78,9 @@ mod should {
         let computed = std::fs::read(tmpfile.path()).unwrap();
 
         match to_fmt {
-            Formats::Json | Formats::HexString => assert_eq!(to_str(&expected), to_str(&computed)),
+            Formats::Json | Formats::HexString | Formats::Calldata => {
+                assert_eq!(to_str(&expected), to_str(&computed))
+            }
             Formats::Bytes => assert_eq!(expected, computed),
         }
     }
@@ -80,7 +88,8 @@ mod should {
     #[rstest]
     fn convert_to_std_out(
         mut bin: Command,
-        #[values(Formats::Json, Formats::Bytes, Formats::HexString)] from_fmt: Formats,
+        #[values(Formats::Json, Formats::Bytes, Formats::HexString, Formats::Calldata)]
+        from_fmt: Formats,
         #[values(Formats::Json, Formats::Bytes, Formats::HexString)] to_fmt: Formats,
     ) {
         let output = bin
@@ -103,11 +112,65 @@ mod should {
         let computed = output.stdout;
 
         match to_fmt {
-            Formats::Json | Formats::HexString => assert_eq!(to_str(&expected), to_str(&computed)),
+            Formats::Json | Formats::HexString | Formats::Calldata => {
+                assert_eq!(to_str(&expected), to_str(&computed))
+            }
             Formats::Bytes => assert_eq!(expected, computed),
         }
     }
 
+    #[rstest]
+    fn convert_to_calldata_with_the_given_public_inputs(
+        mut bin: Command,
+        #[values(Formats::Json, Formats::Bytes, Formats::HexString)] from_fmt: Formats,
+        #[values(VALID_PUBS_DEC, VALID_PUBS_HEX)] pubs: &str,
+    ) {
+        let output = bin
+            .arg("-i")
+            .arg(from_fmt.name())
+            .arg("-o")
+            .arg(Formats::Calldata.name())
+            .arg("--pub")
+            .arg(pubs)
+            .arg(from_fmt.resource_path())
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "STDERR: {}",
+            std::str::from_utf8(output.stderr.as_slice()).unwrap_or("Cannot show output")
+        );
+
+        let expected = std::fs::read(Formats::Calldata.expected_path()).unwrap();
+
+        assert_eq!(to_str(&expected), to_str(&output.stdout));
+    }
+
+    #[rstest]
+    fn convert_calldata_replacing_its_public_inputs(mut bin: Command) {
+        let output = bin
+            .arg("-i")
+            .arg(Formats::Calldata.name())
+            .arg("-o")
+            .arg(Formats::Calldata.name())
+            .arg("--pub")
+            .arg("1")
+            .arg("--pub")
+            .arg("2")
+            .arg(Formats::Calldata.resource_path())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+
+        let hex = std::fs::read(Formats::HexString.resource_path()).unwrap();
+        let computed = to_str(&output.stdout);
+
+        assert!(computed.contains(&to_str(&hex)[2..]));
+        assert!(computed.ends_with(&format!("{:064x}{:064x}", 1, 2)));
+    }
+
     #[rstest]
     fn use_json_as_default_format_input_and_hex_as_default_format_output(mut bin: Command) {
         let output = bin.arg(Formats::Json.resource_path()).output().unwrap();
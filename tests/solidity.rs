@@ -0,0 +1,109 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(all(feature = "solidity", feature = "serde"))]
+
+//! Compile the generated `FflonkVerifier` with `solc` and run its `verifyProof` on the
+//! `resources/bins/proof.calldata` proof in revm. The tests are ignored because they need
+//! `solc` on the `PATH`: run them by `cargo test --all-features --test solidity -- --ignored`.
+
+use std::process::Command;
+
+use fflonk_verifier::{solidity_verifier, VerificationKey};
+use revm::{
+    db::InMemoryDB,
+    primitives::{
+        address, AccountInfo, Address, Bytecode, Bytes, ExecutionResult, Output, TransactTo,
+    },
+    Evm,
+};
+
+const VERIFIER: Address = address!("00000000000000000000000000000000000f1011");
+
+fn compile_verifier() -> Vec<u8> {
+    let vk: VerificationKey =
+        serde_json::from_str(include_str!("../resources/bins/verification_key.json")).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("FflonkVerifier.sol");
+    std::fs::write(&source, solidity_verifier(&vk)).unwrap();
+
+    let status = Command::new("solc")
+        .arg("--optimize")
+        .arg("--bin-runtime")
+        .arg("-o")
+        .arg(dir.path())
+        .arg(&source)
+        .status()
+        .expect("solc should be on the PATH");
+    assert!(
+        status.success(),
+        "solc cannot compile the generated contract"
+    );
+
+    let bin = std::fs::read_to_string(dir.path().join("FflonkVerifier.bin-runtime")).unwrap();
+    hex::decode(bin.trim()).unwrap()
+}
+
+fn calldata() -> Vec<u8> {
+    let data = include_str!("../resources/bins/proof.calldata");
+    hex::decode(data.trim().trim_start_matches("0x")).unwrap()
+}
+
+/// Call the contract and return the `verifyProof` result.
+fn verify_proof(code: Vec<u8>, calldata: Vec<u8>) -> bool {
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(
+        VERIFIER,
+        AccountInfo {
+            code: Some(Bytecode::new_raw(Bytes::from(code))),
+            ..Default::default()
+        },
+    );
+    let mut evm = Evm::builder()
+        .with_db(db)
+        .modify_tx_env(|tx| {
+            tx.transact_to = TransactTo::Call(VERIFIER);
+            tx.data = Bytes::from(calldata);
+        })
+        .build();
+
+    match evm.transact().unwrap().result {
+        ExecutionResult::Success {
+            output: Output::Call(output),
+            ..
+        } => {
+            assert_eq!(32, output.len(), "verifyProof should return a bool");
+            output[31] == 1
+        }
+        result => panic!("verifyProof failed: {result:?}"),
+    }
+}
+
+#[test]
+#[ignore = "needs solc on the PATH"]
+fn accept_the_valid_proof() {
+    assert!(verify_proof(compile_verifier(), calldata()));
+}
+
+#[test]
+#[ignore = "needs solc on the PATH"]
+fn reject_a_tampered_proof() {
+    let mut calldata = calldata();
+    // The last byte of the `a` evaluation: the proof words start after the selector
+    calldata[4 + 32 * 17 - 1] ^= 1;
+
+    assert!(!verify_proof(compile_verifier(), calldata));
+}
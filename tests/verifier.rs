@@ -32,11 +32,13 @@ mod should {
     use super::*;
     use crate::formats::Formats;
 
-    const VALID_VK: &'static str = "resources/bins/verification_key.json";
-    const ZKSYNC_VK: &'static str = "resources/bins/zksync_vk.json";
-    const VALID_PUBS_HEX: &'static str =
+    const VALID_VK: &str = "resources/bins/verification_key.json";
+    // Synthetic: just the zkey header sections of `VALID_VK`.
+    const VALID_ZKEY: &str = "resources/bins/verification_key.zkey";
+    const ZKSYNC_VK: &str = "resources/bins/zksync_vk.json";
+    const VALID_PUBS_HEX: &str =
         "0x110d778eaf8b8ef7ac10f8ac239a14df0eb292a8d1b71340d527b26301a9ab08";
-    const VALID_PUBS_DEC: &'static str =
+    const VALID_PUBS_DEC: &str =
         "7713112592372404476342535432037683616424591277138491596200192981572885523208";
 
     #[rstest]
@@ -51,7 +53,8 @@ mod should {
     #[rstest]
     fn verify(
         mut bin: Command,
-        #[values(Formats::Json, Formats::Bytes, Formats::HexString)] proof: Formats,
+        #[values(Formats::Json, Formats::Bytes, Formats::HexString, Formats::Calldata)]
+        proof: Formats,
         #[values(VALID_PUBS_DEC, VALID_PUBS_HEX)] pubs: &str,
     ) {
         let output = bin
@@ -124,6 +127,95 @@ mod should {
         assert!(!output.stderr.is_empty());
     }
 
+    #[rstest]
+    fn reject_proofs_with_more_public_inputs(mut bin: Command) {
+        let proof = Formats::Json;
+        let output = bin
+            .arg("-p")
+            .arg(proof.name())
+            .arg(Path::new(VALID_VK))
+            .arg(proof.resource_path())
+            .arg(VALID_PUBS_HEX)
+            .arg("123")
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "Should fail");
+        assert!(!output.stderr.is_empty());
+    }
+
+    #[rstest]
+    fn verify_calldata_with_its_public_inputs(mut bin: Command) {
+        let proof = Formats::Calldata;
+        let output = bin
+            .arg("-p")
+            .arg(proof.name())
+            .arg(Path::new(VALID_VK))
+            .arg(proof.resource_path())
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "STDERR: {}",
+            std::str::from_utf8(output.stderr.as_slice()).unwrap_or("Cannot show output")
+        );
+        assert!(to_str(&output.stdout).contains("verified"));
+    }
+
+    #[rstest]
+    fn reject_calldata_with_wrong_public_inputs(mut bin: Command) {
+        let proof = Formats::Calldata;
+        let output = bin
+            .arg("-p")
+            .arg(proof.name())
+            .arg(Path::new(VALID_VK))
+            .arg(proof.resource_path())
+            .arg("123")
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "Should fail");
+        assert!(!output.stderr.is_empty());
+    }
+
+    #[rstest]
+    fn dump_the_verification_trace(mut bin: Command) {
+        let output = bin
+            .arg("--trace")
+            .arg(Path::new(VALID_VK))
+            .arg(Formats::HexString.resource_path())
+            .arg(VALID_PUBS_HEX)
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "STDERR: {}",
+            std::str::from_utf8(output.stderr.as_slice()).unwrap_or("Cannot show output")
+        );
+        let trace: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        for name in [
+            "beta", "gamma", "xi", "alpha", "y", "zh", "L1", "PI", "r0", "r1", "r2", "F", "E", "J",
+        ] {
+            assert!(trace.get(name).is_some(), "Missing {name} in {trace}");
+        }
+    }
+
+    #[rstest]
+    fn fail_also_when_dumping_the_trace(mut bin: Command) {
+        let output = bin
+            .arg("--trace")
+            .arg(Path::new(ZKSYNC_VK))
+            .arg(Formats::HexString.resource_path())
+            .arg(VALID_PUBS_HEX)
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "Should fail");
+        assert!(!output.stderr.is_empty());
+    }
+
     #[rstest]
     fn use_hex_as_default_format(mut bin: Command) {
         let output = bin
@@ -141,6 +233,134 @@ mod should {
         assert!(output.stderr.is_empty());
     }
 
+    #[rstest]
+    fn export_the_verification_key_from_zkey(mut bin: Command) {
+        let output = bin
+            .arg("vk-export")
+            .arg(Path::new(VALID_ZKEY))
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "STDERR: {}",
+            std::str::from_utf8(output.stderr.as_slice()).unwrap_or("Cannot show output")
+        );
+        let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let expected: serde_json::Value =
+            serde_json::from_reader(std::fs::File::open(VALID_VK).unwrap()).unwrap();
+        for field in [
+            "power", "k1", "k2", "w", "w3", "w4", "w8", "wr", "X_2", "C0",
+        ] {
+            assert_eq!(expected[field], exported[field], "Field {field} differs");
+        }
+    }
+
+    #[rstest]
+    fn verify_proof_with_the_exported_verification_key(mut bin: Command) {
+        let dir = tempfile::tempdir().unwrap();
+        let vk = dir.path().join("vk.json");
+        let export = bin
+            .arg("vk-export")
+            .arg(Path::new(VALID_ZKEY))
+            .arg(&vk)
+            .output()
+            .unwrap();
+        assert!(export.status.success());
+
+        let output = super::bin()
+            .arg(&vk)
+            .arg(Formats::HexString.resource_path())
+            .arg(VALID_PUBS_HEX)
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "STDERR: {}",
+            std::str::from_utf8(output.stderr.as_slice()).unwrap_or("Cannot show output")
+        );
+    }
+
+    #[rstest]
+    fn reject_exporting_invalid_zkey(mut bin: Command) {
+        let output = bin
+            .arg("vk-export")
+            .arg(Path::new(VALID_VK))
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "Should fail");
+        assert!(!output.stderr.is_empty());
+    }
+
+    #[rstest]
+    fn check_a_valid_verification_key(mut bin: Command) {
+        let output = bin
+            .arg("check-vk")
+            .arg(Path::new(ZKSYNC_VK))
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "STDERR: {}",
+            std::str::from_utf8(output.stderr.as_slice()).unwrap_or("Cannot show output")
+        );
+        assert!(to_str(&output.stdout)
+            .contains("0x0c94f11324c07919a21cf854213b72023fed8d247b1de903daffaf9d91b1f959"));
+    }
+
+    #[rstest]
+    fn reject_an_inconsistent_verification_key(mut bin: Command) {
+        let dir = tempfile::tempdir().unwrap();
+        let vk = dir.path().join("vk.json");
+        std::fs::write(
+            &vk,
+            std::fs::read_to_string(ZKSYNC_VK)
+                .unwrap()
+                .replace(r#""k2": "3""#, r#""k2": "2""#),
+        )
+        .unwrap();
+
+        let output = bin.arg("check-vk").arg(&vk).output().unwrap();
+
+        assert!(!output.status.success(), "Should fail");
+        assert!(to_str(&output.stderr).contains("'k2': it's not a coset separator"));
+    }
+
+    #[rstest]
+    fn export_the_solidity_verifier(mut bin: Command) {
+        let output = bin
+            .arg("solidity-export")
+            .arg(Path::new(VALID_VK))
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "STDERR: {}",
+            std::str::from_utf8(output.stderr.as_slice()).unwrap_or("Cannot show output")
+        );
+        let source = to_str(&output.stdout);
+        assert!(source.contains("contract FflonkVerifier"));
+        assert!(source.contains(
+            "uint256 constant W1 = 0x027a358499c5042bb4027fd7a5355d71b8c12c177494f0cad00a58f9769a2ee2;"
+        ));
+    }
+
+    #[rstest]
+    fn reject_exporting_solidity_verifier_for_invalid_key(mut bin: Command) {
+        let output = bin
+            .arg("solidity-export")
+            .arg(Formats::Json.resource_path())
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "Should fail");
+        assert!(!output.stderr.is_empty());
+    }
+
     fn to_str(data: &[u8]) -> String {
         std::str::from_utf8(data).unwrap().to_string()
     }
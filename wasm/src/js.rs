@@ -0,0 +1,57 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The JavaScript functions: they throw an `Error` on failure.
+
+use wasm_bindgen::prelude::*;
+
+/// Verify the `proof` with the snarkjs' json verification key `vk_json` against the public
+/// inputs `pubs`: if `pubs` is empty the calldata ones are used.
+#[wasm_bindgen]
+pub fn verify(vk_json: &str, proof: &str, pubs: Vec<String>) -> Result<(), JsError> {
+    Ok(crate::verify(vk_json, proof, &pubs)?)
+}
+
+/// Convert the `proof` to the snarkjs' json.
+#[wasm_bindgen(js_name = proofToJson)]
+pub fn proof_to_json(proof: &str) -> Result<String, JsError> {
+    Ok(crate::proof_to_json(proof)?)
+}
+
+/// Convert the `proof` to the `0x` prefixed hex string of its bytes.
+#[wasm_bindgen(js_name = proofToHex)]
+pub fn proof_to_hex(proof: &str) -> Result<String, JsError> {
+    Ok(crate::proof_to_hex(proof)?)
+}
+
+/// Convert the `proof` to its 768 bytes.
+#[wasm_bindgen(js_name = proofToBytes)]
+pub fn proof_to_bytes(proof: &str) -> Result<Vec<u8>, JsError> {
+    Ok(crate::proof_to_bytes(proof)?)
+}
+
+/// Read the proof from its 768 bytes and convert it to the snarkjs' json.
+#[wasm_bindgen(js_name = proofFromBytes)]
+pub fn proof_from_bytes(bytes: &[u8]) -> Result<String, JsError> {
+    Ok(crate::proof_from_bytes(bytes)?)
+}
+
+/// Convert the `proof` and the public inputs `pubs` to the `0x` prefixed hex string of the
+/// `verifyProof` calldata: if `pubs` is empty the calldata ones are used.
+#[wasm_bindgen(js_name = proofToCalldata)]
+pub fn proof_to_calldata(proof: &str, pubs: Vec<String>) -> Result<String, JsError> {
+    Ok(crate::proof_to_calldata(proof, &pubs)?)
+}
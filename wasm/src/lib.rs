@@ -0,0 +1,209 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! WebAssembly bindings of [`fflonk_verifier`]: verify the BN254 fflonk proofs and convert
+//! them between the `proof-converter` formats from JavaScript.
+//!
+//! The proofs are given as the snarkjs' json, as the hex string of their 768 bytes or as the
+//! hex string of the `verifyProof` EVM ABI calldata, that also contains the public inputs.
+//! The public inputs are hex (`0x` prefixed) or decimal strings.
+
+use fflonk_verifier::{CalldataError, Proof, ProofDataError, Public, VerificationKey, VerifyError};
+use snafu::Snafu;
+
+pub mod js;
+
+/// Bindings error: in JavaScript it's thrown as an `Error` with the same message.
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid verification key json: {error}"))]
+    InvalidKey { error: serde_json::Error },
+    #[snafu(display("Invalid proof json: {error}"))]
+    InvalidJson { error: serde_json::Error },
+    #[snafu(display("Invalid proof hex string: {error}"))]
+    InvalidHex { error: hex::FromHexError },
+    #[snafu(display("Invalid proof: {error}"))]
+    InvalidProof { error: ProofDataError },
+    #[snafu(display("Invalid verifyProof calldata: {error}"))]
+    InvalidCalldata { error: CalldataError },
+    #[snafu(display("Invalid public input '{value}'"))]
+    InvalidPublicInput { value: String },
+    #[snafu(display("Invalid proof: {error}"))]
+    Verify { error: VerifyError },
+}
+
+/// Verify the `proof` with the snarkjs' json verification key `vk_json` against the public
+/// inputs `pubs`: if `pubs` is empty the calldata ones are used.
+pub fn verify(vk_json: &str, proof: &str, pubs: &[String]) -> Result<(), Error> {
+    let vk: VerificationKey =
+        serde_json::from_str(vk_json).map_err(|error| Error::InvalidKey { error })?;
+    let (proof, calldata_pubs) = read_proof(proof)?;
+    let pubs = read_pubs(pubs, calldata_pubs)?;
+    fflonk_verifier::verify(&vk, &proof, &pubs).map_err(|error| Error::Verify { error })
+}
+
+/// Convert the `proof` to the snarkjs' json.
+pub fn proof_to_json(proof: &str) -> Result<String, Error> {
+    let (proof, _) = read_proof(proof)?;
+    Ok(serde_json::to_string_pretty(&proof).expect("Proof serialization cannot fail"))
+}
+
+/// Convert the `proof` to the `0x` prefixed hex string of its bytes.
+pub fn proof_to_hex(proof: &str) -> Result<String, Error> {
+    proof_to_bytes(proof).map(|bytes| format!("0x{}", hex::encode(bytes)))
+}
+
+/// Convert the `proof` to its 768 bytes.
+pub fn proof_to_bytes(proof: &str) -> Result<Vec<u8>, Error> {
+    read_proof(proof).map(|(proof, _)| proof.to_bytes())
+}
+
+/// Read the proof from its 768 bytes and convert it to the snarkjs' json.
+pub fn proof_from_bytes(bytes: &[u8]) -> Result<String, Error> {
+    let proof = <Proof>::from_bytes(bytes).map_err(|error| Error::InvalidProof { error })?;
+    Ok(serde_json::to_string_pretty(&proof).expect("Proof serialization cannot fail"))
+}
+
+/// Convert the `proof` and the public inputs `pubs` to the `0x` prefixed hex string of the
+/// `verifyProof` calldata: if `pubs` is empty the calldata ones are used.
+pub fn proof_to_calldata(proof: &str, pubs: &[String]) -> Result<String, Error> {
+    let (proof, calldata_pubs) = read_proof(proof)?;
+    let pubs = read_pubs(pubs, calldata_pubs)?;
+    Ok(format!("0x{}", hex::encode(proof.encode_calldata(&pubs))))
+}
+
+fn read_proof(proof: &str) -> Result<(Proof, Option<Public>), Error> {
+    let proof = proof.trim();
+    if proof.starts_with('{') {
+        return serde_json::from_str(proof)
+            .map(|proof| (proof, None))
+            .map_err(|error| Error::InvalidJson { error });
+    }
+    let bytes = hex::decode(proof.strip_prefix("0x").unwrap_or(proof))
+        .map_err(|error| Error::InvalidHex { error })?;
+    if bytes.len() == <Proof>::ENCODED_LEN {
+        <Proof>::from_bytes(&bytes)
+            .map(|proof| (proof, None))
+            .map_err(|error| Error::InvalidProof { error })
+    } else {
+        Proof::decode_calldata(&bytes)
+            .map(|(proof, pubs)| (proof, Some(pubs)))
+            .map_err(|error| Error::InvalidCalldata { error })
+    }
+}
+
+fn read_pubs(pubs: &[String], calldata_pubs: Option<Public>) -> Result<Public, Error> {
+    match calldata_pubs {
+        Some(calldata_pubs) if pubs.is_empty() => Ok(calldata_pubs),
+        _ => pubs
+            .iter()
+            .map(|value| {
+                ethnum::U256::from_str_prefixed(value)
+                    .map(ethnum::U256::to_be_bytes)
+                    .map_err(|_| Error::InvalidPublicInput {
+                        value: value.clone(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|words| {
+                Public::try_from(words.concat().as_slice())
+                    .expect("Cannot fail: is a sequence of 32-byte words")
+            }),
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    const VK: &str = include_str!("../../resources/bins/verification_key.json");
+    const JSON: &str = include_str!("../../resources/bins/proof.json");
+    const HEX: &str = include_str!("../../resources/bins/proof.hex");
+    const CALLDATA: &str = include_str!("../../resources/bins/proof.calldata");
+    const PUB: &str = "0x110d778eaf8b8ef7ac10f8ac239a14df0eb292a8d1b71340d527b26301a9ab08";
+
+    fn pubs() -> Vec<String> {
+        vec![PUB.to_owned()]
+    }
+
+    #[test]
+    fn verify_proofs_in_every_format() {
+        verify(VK, JSON, &pubs()).unwrap();
+        verify(VK, HEX, &pubs()).unwrap();
+        verify(VK, CALLDATA, &[]).unwrap();
+        verify(VK, CALLDATA, &pubs()).unwrap();
+    }
+
+    #[test]
+    fn accept_decimal_public_inputs() {
+        let decimal = ethnum::U256::from_str_prefixed(PUB).unwrap().to_string();
+
+        verify(VK, HEX, &[decimal]).unwrap();
+    }
+
+    #[test]
+    fn reject_wrong_public_inputs() {
+        assert!(matches!(
+            verify(VK, HEX, &["0x1234".to_owned()]),
+            Err(Error::Verify { .. })
+        ));
+        assert!(matches!(verify(VK, HEX, &[]), Err(Error::Verify { .. })));
+        assert!(matches!(
+            verify(VK, HEX, &["not a number".to_owned()]),
+            Err(Error::InvalidPublicInput { .. })
+        ));
+    }
+
+    #[test]
+    fn report_invalid_inputs() {
+        assert!(matches!(
+            verify("{}", HEX, &pubs()),
+            Err(Error::InvalidKey { .. })
+        ));
+        assert!(matches!(
+            proof_to_hex("{\"polynomials\": 1}"),
+            Err(Error::InvalidJson { .. })
+        ));
+        assert!(matches!(
+            proof_to_hex("0xzz"),
+            Err(Error::InvalidHex { .. })
+        ));
+        assert!(matches!(
+            proof_to_hex("0x1234"),
+            Err(Error::InvalidCalldata { .. })
+        ));
+        assert!(matches!(
+            proof_from_bytes(&[0; 32]),
+            Err(Error::InvalidProof { .. })
+        ));
+    }
+
+    #[test]
+    fn convert_the_proofs() {
+        assert_eq!(HEX.trim(), proof_to_hex(JSON).unwrap());
+        assert_eq!(HEX.trim(), proof_to_hex(CALLDATA).unwrap());
+        assert_eq!(CALLDATA.trim(), proof_to_calldata(JSON, &pubs()).unwrap());
+        assert_eq!(CALLDATA.trim(), proof_to_calldata(CALLDATA, &[]).unwrap());
+        assert_eq!(
+            proof_to_json(JSON).unwrap(),
+            proof_from_bytes(&proof_to_bytes(HEX).unwrap()).unwrap()
+        );
+        assert_eq!(
+            HEX.trim(),
+            proof_to_hex(&proof_to_json(HEX).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2024, Horizen Labs, Inc.
+// Copyright 2021 0KIMS association.
+//
+// fflonk_verifier is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// fflonk_verifier is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with fflonk_verifier.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The JavaScript bindings tests: run them in a headless browser by
+//! `wasm-pack test --headless --firefox` (or `--chrome`).
+
+#![cfg(target_arch = "wasm32")]
+
+use fflonk_verifier_wasm::js::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const VK: &str = include_str!("../../resources/bins/verification_key.json");
+const JSON: &str = include_str!("../../resources/bins/proof.json");
+const HEX: &str = include_str!("../../resources/bins/proof.hex");
+const CALLDATA: &str = include_str!("../../resources/bins/proof.calldata");
+const PUB: &str = "0x110d778eaf8b8ef7ac10f8ac239a14df0eb292a8d1b71340d527b26301a9ab08";
+
+#[wasm_bindgen_test]
+fn should_verify_valid_proofs() {
+    verify(VK, JSON, vec![PUB.to_owned()]).unwrap();
+    verify(VK, HEX, vec![PUB.to_owned()]).unwrap();
+    verify(VK, CALLDATA, vec![]).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn should_throw_on_invalid_proofs() {
+    assert!(verify(VK, HEX, vec!["0x1234".to_owned()]).is_err());
+    assert!(verify(VK, "0xzz", vec![PUB.to_owned()]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn should_convert_proofs() {
+    assert_eq!(HEX.trim(), proof_to_hex(JSON).unwrap());
+    assert_eq!(
+        CALLDATA.trim(),
+        proof_to_calldata(JSON, vec![PUB.to_owned()]).unwrap()
+    );
+    assert_eq!(
+        proof_to_json(HEX).unwrap(),
+        proof_from_bytes(&proof_to_bytes(CALLDATA).unwrap()).unwrap()
+    );
+}